@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Approximate, human-friendly formatting, such as `about 2 hours` or `just over a day`.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::{approx, duration::Container};
+//!
+//! let container = Container::from(Duration::from_secs(6_900));
+//! let formatted = approx::format(&container, &approx::Options::default())
+//!     .expect("Could not format duration");
+//! assert_eq!(formatted, "about 2 hours");
+//! ```
+
+use crate::{
+    duration::{Container, Unit},
+    error,
+    locale::ENGLISH,
+};
+
+// The units approximate durations are given in, largest first
+const UNITS: [Unit; 10] = [
+    Unit::Year,
+    Unit::Month,
+    Unit::Week,
+    Unit::Day,
+    Unit::Hour,
+    Unit::Minute,
+    Unit::Second,
+    Unit::Millisecond,
+    Unit::Microsecond,
+    Unit::Nanosecond,
+];
+
+/// Which way in time an approximate duration points.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Tense {
+    /// Just the duration, e.g. `about 5 minutes`
+    #[default]
+    Plain,
+    /// In the future, e.g. `in about 5 minutes`
+    Future,
+    /// In the past, e.g. `about 5 minutes ago`
+    Past,
+    /// In the future for positive durations, and in the past for negative ones
+    Auto,
+}
+
+/// How the count of an approximate duration is rounded.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round to the nearest count, e.g. `about 2 hours` for 1h 50min
+    #[default]
+    Nearest,
+    /// Round down, e.g. `over an hour` for 1h 50min
+    Down,
+    /// Round up, e.g. `under 2 hours` for 1h 10min
+    Up,
+}
+
+/// Options for approximate formatting.
+#[derive(Copy, Clone, Debug)]
+pub struct Options {
+    /// Which way in time the duration points
+    pub tense: Tense,
+    /// How the count is rounded
+    pub rounding: Rounding,
+    /// The smallest unit used. Anything shorter is `less than a minute`, or similar.
+    pub smallest_unit: Unit,
+    /// How much of a unit a duration must reach before that unit is used, above 0 and up to 1.
+    /// With 0.9, 55 minutes is `about an hour`, but 50 minutes is `50 minutes`. Anything outside
+    /// that range, such as 0, is treated as 1.
+    pub threshold: f64,
+    /// How far over a whole count is described as `just over` it, as a fraction of the count.
+    pub just_over: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            tense: Tense::Plain,
+            rounding: Rounding::Nearest,
+            smallest_unit: Unit::Minute,
+            threshold: 0.9,
+            just_over: 0.1,
+        }
+    }
+}
+
+// A count of a unit in words, such as `an hour` or `5 minutes`
+fn count(count: f64, unit: Unit) -> String {
+    // Only "hour" starts with a vowel sound
+    let article = if unit == Unit::Hour { "an" } else { "a" };
+    if (count - 1.0).abs() < f64::EPSILON {
+        format!("{article} {}", ENGLISH.name(unit, 1.0))
+    } else {
+        format!("{count} {}", ENGLISH.name(unit, count))
+    }
+}
+
+// Describe a length of time without a tense
+fn describe(nanos: u128, options: &Options) -> String {
+    let smallest = options.smallest_unit;
+    if nanos < smallest.nanos() {
+        return format!("less than {}", count(1.0, smallest));
+    }
+
+    // A threshold of 0 would pick the largest unit for any duration
+    let threshold = if options.threshold > 0.0 {
+        options.threshold.min(1.0)
+    } else {
+        1.0
+    };
+
+    // Use the largest unit the duration comes close enough to
+    #[allow(clippy::cast_precision_loss)]
+    let (unit, value) = UNITS
+        .iter()
+        .filter(|unit| unit.nanos() >= smallest.nanos())
+        .map(|&unit| (unit, nanos as f64 / unit.nanos() as f64))
+        .find(|&(_, value)| value >= threshold)
+        .unwrap_or((smallest, 1.0));
+
+    let rounded = match options.rounding {
+        Rounding::Nearest => value.round(),
+        Rounding::Down => value.floor(),
+        Rounding::Up => value.ceil(),
+    }
+    .max(1.0);
+
+    let qualifier = if (value - rounded).abs() <= rounded * f64::EPSILON {
+        ""
+    } else if value > rounded && value - rounded <= rounded * options.just_over {
+        "just over "
+    } else if value > rounded && options.rounding == Rounding::Down {
+        "over "
+    } else if value < rounded && options.rounding == Rounding::Up {
+        "under "
+    } else {
+        "about "
+    };
+
+    format!("{qualifier}{}", count(rounded, unit))
+}
+
+// Describe a number of nanoseconds approximately
+fn render(nanos: i128, options: &Options) -> String {
+    let description = describe(nanos.unsigned_abs(), options);
+    match options.tense {
+        Tense::Plain => description,
+        Tense::Past => format!("{description} ago"),
+        Tense::Auto if nanos < 0 => format!("{description} ago"),
+        Tense::Future | Tense::Auto => format!("in {description}"),
+    }
+}
+
+/// Describe a duration approximately, such as `about 2 hours` or `in just over a day`.
+///
+/// The duration is given in the largest unit it comes close enough to, as set by
+/// [`Options::threshold`], with the count rounded to a whole number. Negative durations are
+/// described by their length, unless the tense is [`Tense::Auto`].
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the total duration is too large.
+pub fn format(container: &Container, options: &Options) -> Result<String, error::Error> {
+    Ok(render(container.nanos()?, options))
+}
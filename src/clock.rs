@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Clock-style durations, such as `01:30:00`, `1:02:03.250` or `2d 04:00:00`.
+//!
+//! These are common in spreadsheets and stopwatch exports. A clock has hours, minutes and
+//! seconds, or just minutes and seconds, with an optional fraction of a second. It may be preceded
+//! by a number of days, using any of systemd's names for days, and a sign.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::clock;
+//!
+//! let container = clock::parse("2d 04:00:00").expect("Could not parse duration");
+//! assert_eq!(Duration::try_from(container.clone()).unwrap(), Duration::from_secs(187_200));
+//! assert_eq!(clock::format(&container).unwrap(), "2d 04:00:00");
+//! ```
+
+use nom::{
+    bytes::complete::take_while_m_n,
+    character::complete::{alpha1, char, digit1, multispace0, one_of},
+    combinator::{map_opt, opt, verify},
+    error::{ErrorKind::Verify, ParseError},
+    sequence::{delimited, preceded, terminated},
+    Err::Error,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, format, parser,
+};
+
+// A whole number of any length
+fn number<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(digit1, |s: &str| s.parse().ok()).parse(input)
+}
+
+// Two digits counting minutes or seconds, from 00 to 59
+fn sexagesimal<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    verify(
+        map_opt(
+            take_while_m_n(2, 2, |c: char| c.is_ascii_digit()),
+            |s: &str| s.parse().ok(),
+        ),
+        |&n| n < 60,
+    )
+    .parse(input)
+}
+
+// A number of days before the clock, such as `2d ` or `3 days `
+fn days<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    terminated(
+        number,
+        (
+            multispace0,
+            verify(alpha1, |word: &str| parser::unit(word) == Some(Unit::Day)),
+            multispace0,
+        ),
+    )
+    .parse(input)
+}
+
+// The digits of a fraction of a second, as nanoseconds. Digits past nanoseconds are dropped.
+fn fraction<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(preceded(char('.'), digit1), |digits: &str| {
+        format!("{digits:0<9.9}").parse().ok()
+    })
+    .parse(input)
+}
+
+/// Parse a clock-style duration from the start of the input, returning the remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a clock-style duration. Minutes and
+/// seconds must be two digits below 60, and hours must be below 24 if days are given.
+pub fn clock<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let (input, sign) = opt(one_of("+-")).parse(input)?;
+    let (input, days) = opt(days).parse(input)?;
+    let (input, first) = number(input)?;
+    let (input, second) = preceded(char(':'), sexagesimal).parse(input)?;
+    let (input, third) = opt(preceded(char(':'), sexagesimal)).parse(input)?;
+    let (input, nanos) = opt(fraction).parse(input)?;
+
+    let (hours, minutes, seconds) =
+        third.map_or((0, first, second), |third| (first, second, third));
+    // Days need a full clock after them, which can't reach a whole day
+    if days.is_some() && (third.is_none() || hours >= 24) {
+        return Err(Error(E::from_error_kind(input, Verify)));
+    }
+
+    let sign = if sign == Some('-') { -1.0 } else { 1.0 };
+    #[allow(clippy::cast_precision_loss)]
+    let count = |n: u64| sign * n as f64;
+    let mut durations = Vec::new();
+    if let Some(days) = days {
+        durations.push(Duration::Day(count(days)));
+    }
+    durations.extend([
+        Duration::Hour(count(hours)),
+        Duration::Minute(count(minutes)),
+        Duration::Second(count(seconds)),
+    ]);
+    if let Some(nanos) = nanos {
+        // Fractions are below one second, so always fit
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        durations.push(Duration::Nanosecond(sign as i64 * nanos as i64));
+    }
+
+    Ok((input, Container::new(durations)))
+}
+
+/// Parse an entire string as a clock-style duration, ignoring surrounding whitespace.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a clock-style duration. See [`clock`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(delimited(multispace0, clock, multispace0), input)
+}
+
+/// Format a duration as a clock, such as `01:30:00` or `2d 04:00:00.25`.
+///
+/// Days are only shown for durations of a day or more. The fraction of a second is shown to the
+/// nanosecond, without trailing zeros. The result can be parsed again by [`parse`].
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the total duration is too large.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = container.nanos()?;
+    let sign = if nanos < 0 { "-" } else { "" };
+    Ok(format::clock(
+        sign,
+        nanos.unsigned_abs(),
+        1,
+        Unit::Hour,
+        true,
+    ))
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Suggesting completions for partially typed durations.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::completion::{complete, Completion};
+//!
+//! let names: Vec<_> = complete("15mi", 4)
+//!     .into_iter()
+//!     .filter_map(|c| match c {
+//!         Completion::Unit { name, .. } => Some(name),
+//!         Completion::Number => None,
+//!     })
+//!     .collect();
+//! assert_eq!(&names[..4], ["min", "mins", "minute", "minutes"]);
+//! ```
+
+use std::ops::Range;
+
+use crate::{
+    duration::Unit,
+    parser,
+    token::{tokenize, Token, TokenKind},
+};
+
+/// A possible continuation of a partially typed duration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Completion {
+    /// A number is expected here
+    Number,
+    /// A unit name is valid here
+    Unit {
+        /// The full unit name
+        name: &'static str,
+        /// The unit the name stands for
+        unit: Unit,
+        /// The byte range of the input the name should replace
+        replace: Range<usize>,
+    },
+}
+
+// Every unit name starting with the given prefix, shortest first
+fn units(prefix: &str, replace: &Range<usize>) -> Vec<Completion> {
+    let mut names: Vec<_> = parser::UNITS
+        .iter()
+        .filter(|(name, _)| name.starts_with(prefix))
+        .collect();
+    names.sort_by_key(|(name, _)| name.chars().count());
+
+    names
+        .into_iter()
+        .map(|&(name, unit)| Completion::Unit {
+            name,
+            unit,
+            replace: replace.clone(),
+        })
+        .collect()
+}
+
+/// Find the valid continuations of a duration at the given cursor position.
+///
+/// The cursor is a byte offset into the input. If the cursor is in or after a word following a
+/// number, the unit names starting with the text before the cursor are returned; their
+/// replacement range covers the whole word. If a number is expected at the cursor,
+/// [`Completion::Number`] is returned.
+///
+/// If nothing could follow, such as after an invalid token, or the cursor isn't on a character
+/// boundary, no completions are returned.
+#[must_use]
+pub fn complete(input: &str, cursor: usize) -> Vec<Completion> {
+    let Some(before) = input.get(..cursor) else {
+        return Vec::new();
+    };
+
+    let tokens: Vec<Token> = tokenize(before).collect();
+    let mut significant = tokens
+        .iter()
+        .rev()
+        .filter(|t| t.kind != TokenKind::Whitespace);
+    let last = tokens.last();
+
+    match last.map(|t| t.kind) {
+        None | Some(TokenKind::Sign) => vec![Completion::Number],
+        Some(TokenKind::Number) => units("", &(cursor..cursor)),
+        Some(TokenKind::Whitespace) => match significant.next().map(|t| t.kind) {
+            None | Some(TokenKind::Unit(_) | TokenKind::Sign) => vec![Completion::Number],
+            Some(TokenKind::Number) => units("", &(cursor..cursor)),
+            Some(TokenKind::Whitespace | TokenKind::Invalid) => Vec::new(),
+        },
+        Some(kind @ (TokenKind::Unit(_) | TokenKind::Invalid)) => {
+            // Only words directly after a number can be completed
+            let word = last.map_or(0..0, |t| t.span.clone());
+            significant.next();
+            let after_number = significant
+                .next()
+                .is_some_and(|t| t.kind == TokenKind::Number);
+            if !after_number || !before[word.clone()].starts_with(char::is_alphabetic) {
+                return Vec::new();
+            }
+
+            // Replace the rest of the word after the cursor too
+            let end = input[cursor..]
+                .find(|c: char| !c.is_alphabetic())
+                .map_or(input.len(), |len| cursor + len);
+            let mut completions = units(&before[word.clone()], &(word.start..end));
+            if matches!(kind, TokenKind::Unit(_)) {
+                completions.push(Completion::Number);
+            }
+            completions
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! GNU coreutils duration arguments, as taken by `sleep` and `timeout`, such as `1.5m` or `2d`.
+//!
+//! An argument is a decimal number, such as `1.5`, `.5` or `1e3`, with an optional suffix of `s`,
+//! `m`, `h` or `d`. Hexadecimal numbers aren't accepted, and neither are negative ones. `inf` or
+//! `infinity` means forever, in any case. Like `sleep`, several arguments separated by whitespace
+//! are added together.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::coreutils::{self, Interval};
+//!
+//! assert_eq!(coreutils::to_systemd("1.5m 2d").unwrap(), "2d 1min 30s");
+//! assert_eq!(coreutils::from_systemd("1h 30min").unwrap(), "90m");
+//! assert!(matches!(coreutils::parse("infinity").unwrap(), Interval::Infinite));
+//! ```
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag_no_case,
+    character::complete::{multispace0, multispace1, one_of},
+    combinator::{map, opt, value, verify},
+    error::{ErrorKind::TooLarge, ParseError},
+    multi::separated_list1,
+    number::complete::recognize_float,
+    sequence::delimited,
+    Err::Failure,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, format, parser,
+};
+
+/// A duration which may be forever, as `sleep` and `timeout` accept.
+#[derive(Clone, Debug)]
+pub enum Interval {
+    /// A duration of a fixed length
+    Finite(Container),
+    /// A duration which never ends, written as `infinity`
+    Infinite,
+}
+
+// A number of the given unit, or forever
+#[derive(Clone, Copy)]
+enum Count {
+    Finite(f64),
+    Infinite,
+}
+
+// A non-negative decimal number, or infinity
+fn number<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Count, E> {
+    alt((
+        value(
+            Count::Infinite,
+            alt((tag_no_case("infinity"), tag_no_case("inf"))),
+        ),
+        map(
+            verify(recognize_float, |s: &str| !s.starts_with('-')),
+            |s: &str| Count::Finite(s.parse().unwrap()),
+        ),
+    ))
+    .parse(input)
+}
+
+// The unit of a suffix, which is seconds if there isn't one
+fn suffix<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Unit, E> {
+    map(opt(one_of("smhd")), |suffix| match suffix {
+        Some('m') => Unit::Minute,
+        Some('h') => Unit::Hour,
+        Some('d') => Unit::Day,
+        _ => Unit::Second,
+    })
+    .parse(input)
+}
+
+/// Parse a single coreutils duration argument from the start of the input, returning the
+/// remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a duration argument. Returns a [`Failure`] if
+/// the duration is too large.
+pub fn coreutils<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Interval, E> {
+    let start = input;
+    let (input, (count, unit)) = (number, suffix).parse(input)?;
+
+    let Count::Finite(count) = count else {
+        return Ok((input, Interval::Infinite));
+    };
+    match Duration::from_count(unit, count) {
+        Some(duration) if duration.nanos().is_ok() => {
+            Ok((input, Interval::Finite(Container::new(vec![duration]))))
+        }
+        _ => Err(Failure(E::from_error_kind(start, TooLarge))),
+    }
+}
+
+/// Parse whitespace-separated coreutils duration arguments, adding them together like `sleep`.
+///
+/// The result is [`Interval::Infinite`] if any argument is.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if any argument is not a duration. See [`coreutils`].
+pub fn parse(input: &str) -> Result<Interval, error::Error> {
+    let intervals = parser::parse_all(
+        delimited(
+            multispace0,
+            separated_list1(multispace1, coreutils),
+            multispace0,
+        ),
+        input,
+    )?;
+
+    let mut durations = Vec::new();
+    for interval in intervals {
+        match interval {
+            Interval::Finite(container) => durations.extend_from_slice(container.durations()),
+            Interval::Infinite => return Ok(Interval::Infinite),
+        }
+    }
+    Ok(Interval::Finite(Container::new(durations)))
+}
+
+/// Format a duration as a single coreutils duration argument, such as `90m` or `1.5s`.
+///
+/// The largest of days, hours and minutes which divides the duration exactly is used, and
+/// otherwise seconds, with the fraction shown to the nanosecond. Zero is `0`.
+///
+/// # Errors
+///
+/// Returns [`error::Error::Unrepresentable`] if the duration is negative, and
+/// [`error::Error::DurationOverflow`] if it is too large.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = container.nanos()?;
+    if nanos < 0 {
+        return Err(error::Error::Unrepresentable("coreutils"));
+    }
+
+    let t = nanos.unsigned_abs();
+    if t == 0 {
+        return Ok("0".to_owned());
+    }
+    for (unit, suffix) in [(Unit::Day, 'd'), (Unit::Hour, 'h'), (Unit::Minute, 'm')] {
+        if t % unit.nanos() == 0 {
+            return Ok(format!("{}{suffix}", t / unit.nanos()));
+        }
+    }
+
+    let second = Unit::Second.nanos();
+    Ok(match format::fraction(t % second, second, 1) {
+        Some((frac, width)) => format!("{}.{frac:0width$}s", t / second),
+        None => format!("{}s", t / second),
+    })
+}
+
+/// Convert coreutils duration arguments into a systemd timespan, such as `1.5m` into `1min 30s`.
+///
+/// An infinite duration is `infinity`, as systemd's settings accept.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not duration arguments.
+pub fn to_systemd(input: &str) -> Result<String, error::Error> {
+    match parse(input)? {
+        Interval::Finite(container) => format::systemd(&container),
+        Interval::Infinite => Ok("infinity".to_owned()),
+    }
+}
+
+/// Convert a systemd timespan into a coreutils duration argument, such as `1h 30min` into `90m`.
+///
+/// `infinity` is passed through unchanged.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a systemd duration, or is negative. See
+/// [`format()`].
+pub fn from_systemd(input: &str) -> Result<String, error::Error> {
+    if input.trim() == "infinity" {
+        return Ok("infinity".to_owned());
+    }
+    format(&parser::parse_container(input)?)
+}
@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! A common interface to the duration formats this crate understands, and detection of which
+//! formats a string could be in.
+//!
+//! Each format is a [`Dialect`], which parses into a [`Container`] and formats from one. The
+//! dialects in this crate are listed in [`DIALECTS`], and other crates can implement [`Dialect`]
+//! for their own formats and pass them to [`detect_with`].
+//!
+//! # Example
+//! ```
+//! use systemd_duration::dialect;
+//!
+//! // systemd reads this as 90 seconds, but .NET as 90 days
+//! let report = dialect::detect("90");
+//! assert!(report.names().contains(&"systemd"));
+//! assert!(report.names().contains(&"dotnet"));
+//! assert!(report.is_ambiguous());
+//!
+//! assert!(!dialect::detect("PT90M").is_ambiguous());
+//!
+//! // systemd reads this as a month, but only its case tells it apart from minutes
+//! assert!(dialect::detect("1M").is_ambiguous());
+//! ```
+
+use crate::{
+    clock, coreutils, dotnet, duration::Container, error, eventbridge, ffmpeg, format, go, iso8601,
+    parser, postgres, prometheus,
+};
+
+#[cfg(feature = "with-humantime")]
+use crate::humantime;
+#[cfg(feature = "with-prost")]
+use crate::protobuf;
+
+/// A textual duration format.
+pub trait Dialect {
+    /// A short name for the format, such as `systemd` or `iso8601`.
+    fn name(&self) -> &'static str;
+
+    /// Parse an entire string in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`error::Error`] if the input is not in this format.
+    fn parse(&self, input: &str) -> Result<Container, error::Error>;
+
+    /// Format a duration in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`error::Error`] if the duration cannot be represented in this format.
+    fn format(&self, container: &Container) -> Result<String, error::Error>;
+
+    /// A unit name in the input which a reader ignoring case would take for another unit, such as
+    /// systemd's `M` for months, which would be read as `m` for minutes.
+    ///
+    /// The default is that there is none.
+    fn case_collision(&self, input: &str) -> Option<String> {
+        let _ = input;
+        None
+    }
+}
+
+/// systemd timespans, as in [`parser`] and [`crate::format`]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Systemd;
+
+impl Dialect for Systemd {
+    fn name(&self) -> &'static str {
+        "systemd"
+    }
+
+    fn parse(&self, input: &str) -> Result<Container, error::Error> {
+        parser::parse_container(input)
+    }
+
+    fn format(&self, container: &Container) -> Result<String, error::Error> {
+        format::systemd(container)
+    }
+
+    fn case_collision(&self, input: &str) -> Option<String> {
+        // Lowercase names are read the same either way, so only `M` can be mistaken
+        let container = parser::parse_container(input).ok()?;
+        container
+            .aliases()
+            .iter()
+            .find(|&&alias| {
+                let lower = alias.to_lowercase();
+                lower != alias
+                    && parser::unit(&lower).is_some_and(|unit| parser::unit(alias) != Some(unit))
+            })
+            .map(|&alias| alias.to_owned())
+    }
+}
+
+// A dialect for a module with `parse` and `format` functions
+macro_rules! impl_dialect {
+    ($(#[$attr:meta])* $type:ident, $module:ident) => {
+        #[doc = concat!("The durations of the [`", stringify!($module), "`] module")]
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, Default)]
+        pub struct $type;
+
+        $(#[$attr])*
+        impl Dialect for $type {
+            fn name(&self) -> &'static str {
+                stringify!($module)
+            }
+
+            fn parse(&self, input: &str) -> Result<Container, error::Error> {
+                $module::parse(input)
+            }
+
+            fn format(&self, container: &Container) -> Result<String, error::Error> {
+                $module::format(container)
+            }
+        }
+    };
+}
+
+impl_dialect!(Clock, clock);
+impl_dialect!(Iso8601, iso8601);
+impl_dialect!(Go, go);
+impl_dialect!(Prometheus, prometheus);
+impl_dialect!(Postgres, postgres);
+impl_dialect!(DotNet, dotnet);
+impl_dialect!(Ffmpeg, ffmpeg);
+impl_dialect!(EventBridge, eventbridge);
+impl_dialect!(
+    #[cfg(feature = "with-humantime")]
+    Humantime,
+    humantime
+);
+impl_dialect!(
+    #[cfg(feature = "with-prost")]
+    Protobuf,
+    protobuf
+);
+
+/// The durations of the [`coreutils`] module.
+///
+/// Infinite durations can't be held in a [`Container`], so `infinity` is rejected.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Coreutils;
+
+impl Dialect for Coreutils {
+    fn name(&self) -> &'static str {
+        "coreutils"
+    }
+
+    fn parse(&self, input: &str) -> Result<Container, error::Error> {
+        match coreutils::parse(input)? {
+            coreutils::Interval::Finite(container) => Ok(container),
+            coreutils::Interval::Infinite => Err(error::Error::DurationOverflow),
+        }
+    }
+
+    fn format(&self, container: &Container) -> Result<String, error::Error> {
+        coreutils::format(container)
+    }
+}
+
+/// All the dialects in this crate, with systemd first.
+pub static DIALECTS: &[&(dyn Dialect + Sync)] = &[
+    &Systemd,
+    &Clock,
+    &Iso8601,
+    &Go,
+    &Prometheus,
+    &Postgres,
+    &DotNet,
+    &Ffmpeg,
+    &Coreutils,
+    &EventBridge,
+    #[cfg(feature = "with-humantime")]
+    &Humantime,
+    #[cfg(feature = "with-prost")]
+    &Protobuf,
+];
+
+/// A dialect which accepts a string, and what it reads the string as.
+#[derive(Clone, Debug)]
+pub struct Detection {
+    /// The name of the dialect
+    pub name: &'static str,
+    /// The duration the dialect parsed
+    pub container: Container,
+    /// A unit name which a reader ignoring case would take for another unit. See
+    /// [`Dialect::case_collision`].
+    pub case_collision: Option<String>,
+}
+
+/// The dialects which accept a string, returned by [`detect`].
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Each dialect which accepts the string, in the order they were given
+    pub detections: Vec<Detection>,
+}
+
+impl Report {
+    /// The names of the dialects which accept the string.
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        self.detections
+            .iter()
+            .map(|detection| detection.name)
+            .collect()
+    }
+
+    /// Whether the dialects which accept the string read it as different lengths, such as `90`,
+    /// which is 90 seconds to systemd but 90 days to .NET.
+    ///
+    /// A string is also ambiguous if a reader ignoring case would take a unit in it for another,
+    /// such as `1M`, which systemd reads as a month but such a reader would read as a minute.
+    /// Durations too large to compare are treated as different from any other.
+    #[must_use]
+    pub fn is_ambiguous(&self) -> bool {
+        if self
+            .detections
+            .iter()
+            .any(|detection| detection.case_collision.is_some())
+        {
+            return true;
+        }
+
+        let lengths: Vec<_> = self
+            .detections
+            .iter()
+            .map(|detection| detection.container.nanos().ok())
+            .collect();
+        lengths
+            .windows(2)
+            .any(|pair| pair[0].is_none() || pair[0] != pair[1])
+    }
+}
+
+/// Find which of this crate's dialects accept a string. See [`DIALECTS`].
+#[must_use]
+pub fn detect(input: &str) -> Report {
+    detect_with(
+        input,
+        DIALECTS.iter().map(|&dialect| dialect as &dyn Dialect),
+    )
+}
+
+/// Find which of the given dialects accept a string.
+#[must_use]
+pub fn detect_with<'a>(input: &str, dialects: impl IntoIterator<Item = &'a dyn Dialect>) -> Report {
+    Report {
+        detections: dialects
+            .into_iter()
+            .filter_map(|dialect| {
+                dialect.parse(input).ok().map(|container| Detection {
+                    name: dialect.name(),
+                    container,
+                    case_collision: dialect.case_collision(input),
+                })
+            })
+            .collect(),
+    }
+}
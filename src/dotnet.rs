@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! .NET `TimeSpan` strings in the constant (`c`) format, such as `1.02:03:04.5000000`.
+//!
+//! The format is `[-][d.]hh:mm:ss[.fffffff]`. When parsing, the seconds may be left out, as may
+//! the whole time, leaving just days, so `1.02:03` and `1` are accepted. A `TimeSpan` counts ticks
+//! of 100 nanoseconds in an [`i64`], so durations with a finer fraction can't be formatted.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::dotnet;
+//!
+//! assert_eq!(dotnet::to_systemd("1.02:03:04.5000000").unwrap(), "1d 2h 3min 4.5s");
+//! assert_eq!(dotnet::from_systemd("-30s").unwrap(), "-00:00:30");
+//! ```
+
+use nom::{
+    branch::alt,
+    bytes::complete::take_while_m_n,
+    character::complete::{char, digit1, multispace0},
+    combinator::{map, map_opt, opt, success},
+    error::{ErrorKind::TooLarge, ParseError},
+    sequence::{delimited, preceded},
+    Err::Failure,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, format, parser,
+};
+
+const NANOS_PER_TICK: i128 = 100;
+
+// A number of one or two digits below the given limit
+fn field<'a, E: ParseError<&'a str>>(limit: u64) -> impl Parser<&'a str, Output = u64, Error = E> {
+    map_opt(
+        take_while_m_n(1, 2, |c: char| c.is_ascii_digit()),
+        move |s: &str| s.parse().ok().filter(|&n| n < limit),
+    )
+}
+
+// A number of days
+fn days<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(digit1, |s: &str| s.parse().ok()).parse(input)
+}
+
+// A fraction of a second, in ticks
+fn ticks<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    preceded(
+        char('.'),
+        map_opt(
+            take_while_m_n(1, 7, |c: char| c.is_ascii_digit()),
+            |s: &str| format!("{s:0<7}").parse().ok(),
+        ),
+    )
+    .parse(input)
+}
+
+// The seconds and ticks after the hours and minutes
+fn seconds<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (u64, u64), E> {
+    map(
+        preceded(char(':'), (field(60), opt(ticks))),
+        |(seconds, ticks)| (seconds, ticks.unwrap_or(0)),
+    )
+    .parse(input)
+}
+
+/// Parse a .NET `TimeSpan` from the start of the input, returning the remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a `TimeSpan` in the constant format. Returns
+/// a [`Failure`] if the duration does not fit in a `TimeSpan`.
+pub fn dotnet<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let start = input;
+    let (input, negative) = opt(char('-')).parse(input)?;
+    let (input, (days, hours, minutes, (seconds, ticks))) = alt((
+        (
+            map(days, Some),
+            preceded(char('.'), field(24)),
+            preceded(char(':'), field(60)),
+            map(opt(seconds), Option::unwrap_or_default),
+        ),
+        (
+            success(None),
+            field(24),
+            preceded(char(':'), field(60)),
+            map(opt(seconds), Option::unwrap_or_default),
+        ),
+        map(days, |days| (Some(days), 0, 0, (0, 0))),
+    ))
+    .parse(input)?;
+
+    // The total must fit in a TimeSpan
+    let total = ((i128::from(days.unwrap_or(0)) * 24 + i128::from(hours)) * 60
+        + i128::from(minutes))
+        * 60
+        * 10_000_000
+        + i128::from(seconds) * 10_000_000
+        + i128::from(ticks);
+    let total = if negative.is_some() { -total } else { total };
+    if i64::try_from(total).is_err() {
+        return Err(Failure(E::from_error_kind(start, TooLarge)));
+    }
+
+    let sign = if negative.is_some() { -1.0 } else { 1.0 };
+    #[allow(clippy::cast_precision_loss)]
+    let count = |n: u64| sign * n as f64;
+    let mut durations = Vec::new();
+    if let Some(days) = days {
+        durations.push(Duration::Day(count(days)));
+    }
+    durations.extend([
+        Duration::Hour(count(hours)),
+        Duration::Minute(count(minutes)),
+        Duration::Second(count(seconds)),
+    ]);
+    if ticks > 0 {
+        // Ticks are below one second, so always fit
+        #[allow(clippy::cast_possible_truncation)]
+        let nanos = (total.signum() * i128::from(ticks) * NANOS_PER_TICK) as i64;
+        durations.push(Duration::Nanosecond(nanos));
+    }
+
+    Ok((input, Container::new(durations)))
+}
+
+/// Parse an entire string as a .NET `TimeSpan`, ignoring surrounding whitespace.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a `TimeSpan` in the constant format. See
+/// [`dotnet`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(delimited(multispace0, dotnet, multispace0), input)
+}
+
+/// Format a duration as a .NET `TimeSpan` in the constant format, such as `1.02:03:04.5000000`.
+///
+/// Days are only shown for durations of a day or more, and the fraction only if there is one,
+/// always to seven digits.
+///
+/// # Errors
+///
+/// Returns [`error::Error::Unrepresentable`] if the duration is not a whole number of ticks, and
+/// [`error::Error::DurationOverflow`] if it does not fit in a `TimeSpan`.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = container.nanos()?;
+    if nanos % NANOS_PER_TICK != 0 {
+        return Err(error::Error::Unrepresentable(".NET"));
+    }
+    if i64::try_from(nanos / NANOS_PER_TICK).is_err() {
+        return Err(error::Error::DurationOverflow);
+    }
+
+    let sign = if nanos < 0 { "-" } else { "" };
+    let mut t = nanos.unsigned_abs();
+    let (day, hour, minute, second) = (
+        Unit::Day.nanos(),
+        Unit::Hour.nanos(),
+        Unit::Minute.nanos(),
+        Unit::Second.nanos(),
+    );
+
+    let days = if t >= day {
+        format!("{}.", t / day)
+    } else {
+        String::new()
+    };
+    t %= day;
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        t / hour,
+        t % hour / minute,
+        t % minute / second
+    );
+    let ticks = t % second / NANOS_PER_TICK.unsigned_abs();
+
+    Ok(if ticks > 0 {
+        format!("{sign}{days}{clock}.{ticks:07}")
+    } else {
+        format!("{sign}{days}{clock}")
+    })
+}
+
+/// Convert a .NET `TimeSpan` into a systemd timespan, such as `00:01:30` into `1min 30s`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a `TimeSpan` in the constant format.
+pub fn to_systemd(input: &str) -> Result<String, error::Error> {
+    format::systemd(&parse(input)?)
+}
+
+/// Convert a systemd timespan into a .NET `TimeSpan`, such as `1min 30s` into `00:01:30`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a systemd duration, or cannot be represented as a
+/// `TimeSpan`. See [`format()`].
+pub fn from_systemd(input: &str) -> Result<String, error::Error> {
+    format(&parser::parse_container(input)?)
+}
@@ -9,9 +9,9 @@
 // You should have received a copy of the CC0 Public Domain Dedication along
 // with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
 
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 
-use crate::error;
+use crate::{error, format};
 
 /// A measurement of a given span of time.
 #[derive(Copy, Clone, Debug)]
@@ -28,15 +28,215 @@ pub enum Duration {
     Nanosecond(i64),
 }
 
+/// A unit of time, without any count.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+impl Duration {
+    /// The unit of this duration.
+    #[must_use]
+    pub const fn unit(&self) -> Unit {
+        match self {
+            Self::Year(_) => Unit::Year,
+            Self::Month(_) => Unit::Month,
+            Self::Week(_) => Unit::Week,
+            Self::Day(_) => Unit::Day,
+            Self::Hour(_) => Unit::Hour,
+            Self::Minute(_) => Unit::Minute,
+            Self::Second(_) => Unit::Second,
+            Self::Millisecond(_) => Unit::Millisecond,
+            Self::Microsecond(_) => Unit::Microsecond,
+            Self::Nanosecond(_) => Unit::Nanosecond,
+        }
+    }
+
+    // A count of the given unit. Nanosecond counts are truncated, and must fit in an i64.
+    pub(crate) fn from_count(unit: Unit, count: f64) -> Option<Self> {
+        Some(match unit {
+            Unit::Year => Self::Year(count),
+            Unit::Month => Self::Month(count),
+            Unit::Week => Self::Week(count),
+            Unit::Day => Self::Day(count),
+            Unit::Hour => Self::Hour(count),
+            Unit::Minute => Self::Minute(count),
+            Unit::Second => Self::Second(count),
+            Unit::Millisecond => Self::Millisecond(count),
+            Unit::Microsecond => Self::Microsecond(count),
+            Unit::Nanosecond => {
+                // All numbers are specified as floats, and a 52-bit mantissa is more than enough
+                // for most nanosecond values, so this is fine.
+                #[allow(clippy::cast_precision_loss)]
+                if count < i64::MIN as f64 || count > i64::MAX as f64 {
+                    return None;
+                }
+                #[allow(clippy::cast_possible_truncation)]
+                Self::Nanosecond(count as i64)
+            }
+        })
+    }
+
+    // The length of this duration in nanoseconds, rounded to the nearest nanosecond
+    pub(crate) fn nanos(&self) -> Result<i128, error::Error> {
+        let count = match *self {
+            Self::Nanosecond(count) => return Ok(i128::from(count)),
+            Self::Year(count)
+            | Self::Month(count)
+            | Self::Week(count)
+            | Self::Day(count)
+            | Self::Hour(count)
+            | Self::Minute(count)
+            | Self::Second(count)
+            | Self::Millisecond(count)
+            | Self::Microsecond(count) => count,
+        };
+
+        // Anything this large would overflow once multiplied by the unit
+        if !count.is_finite() || count.abs() >= 1e20 {
+            return Err(error::Error::DurationOverflow);
+        }
+
+        // Split off the whole part, so whole counts are exact
+        let unit_nanos = self.unit().nanos();
+        let whole = count.trunc();
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_possible_wrap,
+            clippy::cast_precision_loss
+        )]
+        let (whole_nanos, frac_nanos) = (
+            whole as i128 * unit_nanos as i128,
+            ((count - whole) * unit_nanos as f64).round() as i128,
+        );
+
+        Ok(whole_nanos + frac_nanos)
+    }
+}
+
+impl Unit {
+    // The length of the unit in nanoseconds
+    pub(crate) fn nanos(self) -> u128 {
+        let nanos = match self {
+            Self::Year => Convert::SECS_PER_YEAR * Convert::NANOS_PER_SEC,
+            Self::Month => Convert::SECS_PER_MONTH * Convert::NANOS_PER_SEC,
+            Self::Week => Convert::SECS_PER_WEEK * Convert::NANOS_PER_SEC,
+            Self::Day => Convert::SECS_PER_DAY * Convert::NANOS_PER_SEC,
+            Self::Hour => Convert::SECS_PER_HOUR * Convert::NANOS_PER_SEC,
+            Self::Minute => Convert::SECS_PER_MIN * Convert::NANOS_PER_SEC,
+            Self::Second => Convert::NANOS_PER_SEC,
+            Self::Millisecond => Convert::NANOS_PER_MILLI,
+            Self::Microsecond => Convert::NANOS_PER_MICRO,
+            Self::Nanosecond => 1.0,
+        };
+
+        // All of these are whole numbers of nanoseconds, and exactly representable
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let nanos = nanos as u128;
+        nanos
+    }
+}
+
+impl Duration {
+    // Write the count, followed by the given name for the unit
+    fn write_with_unit(&self, f: &mut fmt::Formatter<'_>, unit: &str) -> fmt::Result {
+        match self {
+            Self::Year(count)
+            | Self::Month(count)
+            | Self::Week(count)
+            | Self::Day(count)
+            | Self::Hour(count)
+            | Self::Minute(count)
+            | Self::Second(count)
+            | Self::Millisecond(count)
+            | Self::Microsecond(count) => write!(f, "{count}{unit}"),
+            Self::Nanosecond(count) => write!(f, "{count}{unit}"),
+        }
+    }
+}
+
+impl fmt::Display for Duration {
+    /// Format the duration as a number followed by systemd's name for the unit, such as `30min`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_with_unit(f, format::suffix(self.unit()))
+    }
+}
+
 /// A container of durations, which when summed give the total duration.
 #[derive(Clone, Debug)]
-pub struct Container(Vec<Duration>);
+pub struct Container {
+    durations: Vec<Duration>,
+    // The unit names the durations were parsed from, if any
+    aliases: Vec<&'static str>,
+}
 
 impl Container {
     /// Create a new container object from the given durations.
     #[must_use]
     pub const fn new(durations: Vec<Duration>) -> Self {
-        Self(durations)
+        Self {
+            durations,
+            aliases: Vec::new(),
+        }
+    }
+
+    // Create a container which remembers the unit names the durations were parsed from
+    pub(crate) fn with_aliases(durations: Vec<Duration>, aliases: Vec<&'static str>) -> Self {
+        debug_assert_eq!(durations.len(), aliases.len());
+        Self { durations, aliases }
+    }
+
+    // The unit names the durations were parsed from, which is empty if they weren't parsed
+    pub(crate) fn aliases(&self) -> &[&'static str] {
+        &self.aliases
+    }
+
+    /// The durations in the container.
+    #[must_use]
+    pub fn durations(&self) -> &[Duration] {
+        &self.durations
+    }
+
+    // The total length of the durations in nanoseconds
+    pub(crate) fn nanos(&self) -> Result<i128, error::Error> {
+        self.durations.iter().try_fold(0i128, |sum, duration| {
+            sum.checked_add(duration.nanos()?)
+                .ok_or(error::Error::DurationOverflow)
+        })
+    }
+}
+
+impl fmt::Display for Container {
+    /// Format the durations in systemd's syntax, separated by spaces, such as `1h 30min`.
+    ///
+    /// With the alternate flag (`{:#}`), durations which were parsed are shown with the unit names
+    /// they were parsed with instead, such as `1hours 30m`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.durations.is_empty() {
+            return f.write_str("0");
+        }
+
+        for (i, duration) in self.durations.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+
+            match self.aliases.get(i) {
+                Some(alias) if f.alternate() => duration.write_with_unit(f, alias)?,
+                _ => write!(f, "{duration}")?,
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -100,6 +300,19 @@ pub mod stdtime {
         }};
     }
 
+    impl From<std::time::Duration> for Container {
+        /// Convert a [`std::time::Duration`] into seconds and nanoseconds.
+        ///
+        /// Seconds beyond 2<sup>53</sup> lose precision.
+        fn from(duration: std::time::Duration) -> Self {
+            #[allow(clippy::cast_precision_loss)]
+            Self::new(vec![
+                Duration::Second(duration.as_secs() as f64),
+                Duration::Nanosecond(i64::from(duration.subsec_nanos())),
+            ])
+        }
+    }
+
     impl TryFrom<Container> for std::time::Duration {
         type Error = error::Error;
 
@@ -107,7 +320,7 @@ pub mod stdtime {
         fn try_from(durations: Container) -> Result<Self, Self::Error> {
             let mut duration_sum = Self::new(0, 0);
 
-            for duration in &durations.0 {
+            for duration in &durations.durations {
                 duration_sum += match duration {
                     Duration::Year(count) => {
                         duration_ge_second!(Convert::SECS_PER_YEAR, count)
@@ -159,34 +372,58 @@ pub mod chrono {
     macro_rules! duration_ge_second {
         ($secs_per_interval:expr, $count:expr) => {{
             let seconds = ($secs_per_interval) * ($count);
+            #[allow(clippy::cast_precision_loss)]
             if seconds.is_infinite() || seconds > i64::MAX as f64 || seconds < i64::MIN as f64 {
                 return Err(error::Error::DurationOverflow);
             }
+            // Round down, so the nanoseconds are never negative, then round the nanoseconds
             let (seconds, nanos) = (
-                seconds.trunc(),
-                (seconds - seconds.trunc()) * Convert::NANOS_PER_SEC,
+                seconds.floor(),
+                ((seconds - seconds.floor()) * Convert::NANOS_PER_SEC).round(),
             );
-            ::chrono::TimeDelta::new(seconds as i64, nanos as u32).unwrap()
+            // Range checked above
+            #[allow(clippy::cast_possible_truncation)]
+            ::chrono::TimeDelta::new(seconds as i64, 0)
+                .and_then(|delta| {
+                    delta.checked_add(&::chrono::TimeDelta::nanoseconds(nanos as i64))
+                })
+                .ok_or(error::Error::DurationOverflow)?
         }};
     }
 
     macro_rules! duration_lt_second {
         ($nanos_per_interval:expr, $count:expr) => {{
             let nanos = ($nanos_per_interval) * ($count);
+            #[allow(clippy::cast_precision_loss)]
             if nanos.is_infinite() || nanos > i64::MAX as f64 || nanos < i64::MIN as f64 {
                 return Err(error::Error::DurationOverflow);
             }
+            // Range checked above
+            #[allow(clippy::cast_possible_truncation)]
             ::chrono::TimeDelta::nanoseconds(nanos.round() as i64)
         }};
     }
 
+    impl From<::chrono::TimeDelta> for Container {
+        /// Convert a [`::chrono::TimeDelta`] into seconds and nanoseconds.
+        ///
+        /// Seconds beyond 2<sup>53</sup> lose precision.
+        fn from(delta: ::chrono::TimeDelta) -> Self {
+            #[allow(clippy::cast_precision_loss)]
+            Self::new(vec![
+                Duration::Second(delta.num_seconds() as f64),
+                Duration::Nanosecond(i64::from(delta.subsec_nanos())),
+            ])
+        }
+    }
+
     impl TryFrom<Container> for ::chrono::TimeDelta {
         type Error = error::Error;
 
         /// Convert a [`Duration`] into a [`::chrono::TimeDelta`]
         fn try_from(durations: Container) -> Result<Self, Self::Error> {
             let mut duration_sum = Self::new(0, 0).unwrap();
-            for duration in &durations.0 {
+            for duration in &durations.durations {
                 duration_sum += match duration {
                     Duration::Year(count) => {
                         duration_ge_second!(Convert::SECS_PER_YEAR, count)
@@ -237,13 +474,29 @@ pub mod time {
     macro_rules! duration_lt_second {
         ($nanos_per_interval:expr, $count:expr) => {{
             let nanos = ($nanos_per_interval) * ($count);
+            #[allow(clippy::cast_precision_loss)]
             if nanos.is_infinite() || nanos > i64::MAX as f64 || nanos < i64::MIN as f64 {
                 return Err(error::Error::DurationOverflow);
             }
+            // Range checked above
+            #[allow(clippy::cast_possible_truncation)]
             ::time::Duration::nanoseconds(nanos.round() as i64)
         }};
     }
 
+    impl From<::time::Duration> for Container {
+        /// Convert a [`::time::Duration`] into seconds and nanoseconds.
+        ///
+        /// Seconds beyond 2<sup>53</sup> lose precision.
+        fn from(duration: ::time::Duration) -> Self {
+            #[allow(clippy::cast_precision_loss)]
+            Self::new(vec![
+                Duration::Second(duration.whole_seconds() as f64),
+                Duration::Nanosecond(i64::from(duration.subsec_nanoseconds())),
+            ])
+        }
+    }
+
     /// Convert a [`Duration`] into a [`::time::Duration`]
     impl TryFrom<Container> for ::time::Duration {
         type Error = error::Error;
@@ -251,7 +504,7 @@ pub mod time {
         fn try_from(durations: Container) -> Result<Self, Self::Error> {
             let mut duration_sum = Self::new(0, 0);
 
-            for duration in &durations.0 {
+            for duration in &durations.durations {
                 duration_sum += match duration {
                     Duration::Year(count) => {
                         duration_ge_second!(Convert::SECS_PER_YEAR, count)
@@ -286,3 +539,73 @@ pub mod time {
         }
     }
 }
+
+/// Conversions from [`Duration`] into [`prost_types::Duration`]
+#[cfg(feature = "with-prost")]
+pub mod protobuf {
+    use super::{error, Container, Duration, TryFrom};
+
+    // The largest number of seconds in a protobuf duration, about 10,000 years
+    const MAX_SECONDS: i64 = 315_576_000_000;
+
+    const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+    impl From<prost_types::Duration> for Container {
+        /// Convert a [`prost_types::Duration`] into seconds and nanoseconds.
+        ///
+        /// Seconds beyond 2<sup>53</sup> lose precision, but these are out of range for protobuf.
+        fn from(duration: prost_types::Duration) -> Self {
+            #[allow(clippy::cast_precision_loss)]
+            Self::new(vec![
+                Duration::Second(duration.seconds as f64),
+                Duration::Nanosecond(i64::from(duration.nanos)),
+            ])
+        }
+    }
+
+    impl TryFrom<Container> for prost_types::Duration {
+        type Error = error::Error;
+
+        /// Convert a [`Duration`] into a [`prost_types::Duration`]
+        fn try_from(durations: Container) -> Result<Self, Self::Error> {
+            from_nanos(durations.nanos()?)
+        }
+    }
+
+    // Split nanoseconds into seconds and nanoseconds of the same sign, within protobuf's range
+    pub(crate) fn from_nanos(nanos: i128) -> Result<prost_types::Duration, error::Error> {
+        let seconds = i64::try_from(nanos / NANOS_PER_SEC)
+            .ok()
+            .filter(|seconds| seconds.abs() <= MAX_SECONDS)
+            .ok_or(error::Error::DurationOverflow)?;
+
+        // Always below one second
+        #[allow(clippy::cast_possible_truncation)]
+        let nanos = (nanos % NANOS_PER_SEC) as i32;
+
+        Ok(prost_types::Duration { seconds, nanos })
+    }
+}
+
+/// Conversions from [`Duration`] to [`::humantime::Duration`]
+#[cfg(feature = "with-humantime")]
+pub mod humantime {
+    use super::{error, Container, TryFrom};
+
+    impl From<::humantime::Duration> for Container {
+        /// Convert a [`::humantime::Duration`] into seconds and nanoseconds, as for a
+        /// [`std::time::Duration`].
+        fn from(duration: ::humantime::Duration) -> Self {
+            Self::from(std::time::Duration::from(duration))
+        }
+    }
+
+    impl TryFrom<Container> for ::humantime::Duration {
+        type Error = error::Error;
+
+        /// Convert a [`Container`] into a [`::humantime::Duration`]
+        fn try_from(durations: Container) -> Result<Self, Self::Error> {
+            std::time::Duration::try_from(durations).map(Self::from)
+        }
+    }
+}
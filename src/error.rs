@@ -15,6 +15,21 @@ pub enum Error {
     #[error("Duration overflowed")]
     DurationOverflow,
 
+    #[error("Invalid byte {byte:#04x} at offset {offset}")]
+    InvalidByte { byte: u8, offset: usize },
+
+    #[error("Unrecognized duration phrase at offset {offset}")]
+    InvalidPhrase { offset: usize },
+
+    #[error("Ambiguous unit {0:?}")]
+    AmbiguousUnit(String),
+
+    #[error("Duration cannot be represented as a {0} duration")]
+    Unrepresentable(&'static str),
+
+    #[error("Invalid duration: {0}")]
+    InvalidDuration(String),
+
     #[error(transparent)]
     ParserError(#[from] nom::error::Error<String>),
 }
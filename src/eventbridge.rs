@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! AWS `EventBridge` rate expressions, such as `rate(5 minutes)` or `rate(1 hour)`.
+//!
+//! The value must be a positive whole number, and the unit is `minute`, `hour` or `day`. As in
+//! `EventBridge`, a value of 1 takes the singular unit and any other value the plural, so
+//! `rate(1 minutes)` and `rate(5 minute)` are both rejected.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::eventbridge;
+//!
+//! assert_eq!(eventbridge::to_systemd("rate(90 minutes)").unwrap(), "1h 30min");
+//! assert_eq!(eventbridge::from_systemd("2 days").unwrap(), "rate(2 days)");
+//! ```
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, multispace0, multispace1},
+    combinator::{map_opt, value},
+    error::{ErrorKind::Verify, ParseError},
+    sequence::{delimited, preceded},
+    Err::Error,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, format, parser,
+};
+
+// The units EventBridge accepts, largest first, with their singular and plural names
+const UNITS: [(Unit, &str, &str); 3] = [
+    (Unit::Day, "day", "days"),
+    (Unit::Hour, "hour", "hours"),
+    (Unit::Minute, "minute", "minutes"),
+];
+
+// A positive whole number without a sign
+fn number<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(digit1, |s: &str| s.parse().ok().filter(|&n| n > 0)).parse(input)
+}
+
+// The unit of a rate, and whether its name was plural
+fn unit<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (Unit, bool), E> {
+    // Plural names come first, as the singular names are prefixes of them
+    alt((
+        value((Unit::Day, true), tag("days")),
+        value((Unit::Day, false), tag("day")),
+        value((Unit::Hour, true), tag("hours")),
+        value((Unit::Hour, false), tag("hour")),
+        value((Unit::Minute, true), tag("minutes")),
+        value((Unit::Minute, false), tag("minute")),
+    ))
+    .parse(input)
+}
+
+/// Parse an `EventBridge` rate expression from the start of the input, returning the remaining
+/// input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a rate expression, including when the unit
+/// is plural for a value of 1 or singular for any other value.
+pub fn eventbridge<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let (rest, (count, (unit, plural))) = delimited(
+        (tag("rate"), char('('), multispace0),
+        (number, preceded(multispace1, unit)),
+        (multispace0, char(')')),
+    )
+    .parse(input)?;
+
+    if plural != (count != 1) {
+        return Err(Error(E::from_error_kind(input, Verify)));
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let duration = Duration::from_count(unit, count as f64);
+    Ok((rest, Container::new(duration.into_iter().collect())))
+}
+
+/// Parse an entire string as an `EventBridge` rate expression, ignoring surrounding whitespace.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a rate expression. See [`eventbridge`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(delimited(multispace0, eventbridge, multispace0), input)
+}
+
+/// Format a duration as an `EventBridge` rate expression, such as `rate(90 minutes)`.
+///
+/// The largest of days, hours and minutes which divides the duration exactly is used.
+///
+/// # Errors
+///
+/// Returns [`error::Error::Unrepresentable`] if the duration is not a positive whole number of
+/// minutes, and [`error::Error::DurationOverflow`] if it is too large.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = container.nanos()?;
+    if nanos <= 0 || nanos.unsigned_abs() % Unit::Minute.nanos() != 0 {
+        return Err(error::Error::Unrepresentable("EventBridge"));
+    }
+
+    let t = nanos.unsigned_abs();
+    let (count, singular, plural) = UNITS
+        .iter()
+        .find(|(unit, _, _)| t % unit.nanos() == 0)
+        .map(|&(unit, singular, plural)| (t / unit.nanos(), singular, plural))
+        .ok_or(error::Error::Unrepresentable("EventBridge"))?;
+
+    let name = if count == 1 { singular } else { plural };
+    Ok(format!("rate({count} {name})"))
+}
+
+/// Convert an `EventBridge` rate expression into a systemd timespan, such as `rate(90 minutes)`
+/// into `1h 30min`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a rate expression.
+pub fn to_systemd(input: &str) -> Result<String, error::Error> {
+    format::systemd(&parse(input)?)
+}
+
+/// Convert a systemd timespan into an `EventBridge` rate expression, such as `1h 30min` into
+/// `rate(90 minutes)`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a systemd duration, or cannot be represented as a
+/// rate. See [`format()`].
+pub fn from_systemd(input: &str) -> Result<String, error::Error> {
+    format(&parser::parse_container(input)?)
+}
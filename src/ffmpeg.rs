@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! ffmpeg time durations, as taken by options such as `-t` and `-ss`, such as `01:30:00.5` or
+//! `90.5`.
+//!
+//! This follows ffmpeg's `av_parse_time`. There are two forms: `[-][HH:]MM:SS[.m...]`, where
+//! minutes and seconds are below 60, and `[-]S+[.m...]`, a number of seconds. Like `strtoll`, the
+//! number may also have a `+` sign. Either form may be followed by `s`, or by `ms` or `us` to count
+//! milliseconds or microseconds instead, so `1:00ms` is 60 milliseconds. Only the first six digits
+//! of a fraction are read, and the point may have no digits after it, as in `1.`. Durations are
+//! whole microseconds in an [`i64`], so a fraction of microseconds is ignored, as in `1.5us`.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::ffmpeg;
+//!
+//! assert_eq!(ffmpeg::to_systemd("01:30:00.5").unwrap(), "1h 30min 500ms");
+//! assert_eq!(ffmpeg::to_systemd("250ms").unwrap(), "250ms");
+//! assert_eq!(ffmpeg::from_systemd("1h 30min 500ms").unwrap(), "01:30:00.5");
+//! ```
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{char, digit0, digit1, multispace0},
+    combinator::{map, map_opt, opt, success, value},
+    error::{ErrorKind::TooLarge, ParseError},
+    sequence::{delimited, preceded},
+    Err::Failure,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, format, parser,
+};
+
+const NANOS_PER_MICRO: i128 = 1_000;
+
+// A whole number of any length
+fn number<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(digit1, |s: &str| s.parse().ok()).parse(input)
+}
+
+// One or two digits counting minutes or seconds, from 0 to 59
+fn sexagesimal<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(
+        take_while_m_n(1, 2, |c: char| c.is_ascii_digit()),
+        |s: &str| s.parse().ok().filter(|&n| n < 60),
+    )
+    .parse(input)
+}
+
+// A fraction in millionths, which may have no digits. Digits past the sixth are read but ignored.
+fn fraction<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(preceded(char('.'), digit0), |digits: &str| {
+        format!("{digits:0<6.6}").parse().ok()
+    })
+    .parse(input)
+}
+
+// A clock, such as `1:30:00` or `02:30`, or a number of seconds as `strtoll` reads it, as hours,
+// minutes and seconds
+fn clock_or_number<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, (u64, u64, u64), E> {
+    alt((
+        (
+            number,
+            preceded(char(':'), sexagesimal),
+            preceded(char(':'), sexagesimal),
+        ),
+        (success(0), sexagesimal, preceded(char(':'), sexagesimal)),
+        map(preceded((multispace0, opt(char('+'))), number), |seconds| {
+            (0, 0, seconds)
+        }),
+    ))
+    .parse(input)
+}
+
+// The unit the duration counts, which is seconds without a suffix
+fn suffix<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Unit, E> {
+    alt((
+        value(Unit::Millisecond, tag("ms")),
+        value(Unit::Microsecond, tag("us")),
+        value(Unit::Second, opt(char('s'))),
+    ))
+    .parse(input)
+}
+
+/// Parse an ffmpeg time duration from the start of the input, returning the remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with an ffmpeg duration. Returns a [`Failure`] if
+/// the duration does not fit in an [`i64`] of microseconds.
+pub fn ffmpeg<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let start = input;
+    let (input, negative) = opt(char('-')).parse(input)?;
+    let (input, ((hours, minutes, seconds), fraction, unit)) =
+        (clock_or_number, opt(fraction), suffix).parse(input)?;
+    let fraction = fraction.unwrap_or(0);
+
+    // With a suffix, the whole clock counts milliseconds or microseconds, and the fraction is
+    // scaled down to whole microseconds
+    let total = || {
+        hours
+            .checked_mul(3_600)
+            .and_then(|total| total.checked_add(minutes * 60 + seconds))
+            .ok_or_else(|| Failure(E::from_error_kind(start, TooLarge)))
+    };
+    let counts = match unit {
+        Unit::Second => vec![
+            (Unit::Hour, hours),
+            (Unit::Minute, minutes),
+            (Unit::Second, seconds),
+            (Unit::Microsecond, fraction),
+        ],
+        Unit::Millisecond => vec![(unit, total()?), (Unit::Microsecond, fraction / 1_000)],
+        _ => vec![(unit, total()?)],
+    };
+
+    // The total must fit in an i64 of microseconds
+    let total = counts
+        .iter()
+        .try_fold(0u64, |total, &(unit, count)| {
+            let micros = u64::try_from(unit.nanos() / NANOS_PER_MICRO.unsigned_abs()).ok()?;
+            total.checked_add(count.checked_mul(micros)?)
+        })
+        .filter(|&total| i64::try_from(total).is_ok());
+    if total.is_none() {
+        return Err(Failure(E::from_error_kind(start, TooLarge)));
+    }
+
+    let sign = if negative.is_some() { -1.0 } else { 1.0 };
+    #[allow(clippy::cast_precision_loss)]
+    let durations = counts
+        .into_iter()
+        .filter_map(|(unit, count)| Duration::from_count(unit, sign * count as f64))
+        .collect();
+
+    Ok((input, Container::new(durations)))
+}
+
+/// Parse an entire string as an ffmpeg time duration, ignoring surrounding whitespace.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not an ffmpeg duration. See [`ffmpeg`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(delimited(multispace0, ffmpeg, multispace0), input)
+}
+
+/// Format a duration as an ffmpeg time duration, such as `01:30:00.5`.
+///
+/// The format is `[-]HH:MM:SS[.ffffff]`, with the fraction shown to the microsecond, without
+/// trailing zeros.
+///
+/// # Errors
+///
+/// Returns [`error::Error::Unrepresentable`] if the duration is not a whole number of
+/// microseconds, and [`error::Error::DurationOverflow`] if it does not fit in an [`i64`] of
+/// microseconds.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = container.nanos()?;
+    if nanos % NANOS_PER_MICRO != 0 {
+        return Err(error::Error::Unrepresentable("ffmpeg"));
+    }
+    if i64::try_from(nanos / NANOS_PER_MICRO).is_err() {
+        return Err(error::Error::DurationOverflow);
+    }
+
+    let sign = if nanos < 0 { "-" } else { "" };
+    Ok(format::clock(
+        sign,
+        nanos.unsigned_abs(),
+        NANOS_PER_MICRO.unsigned_abs(),
+        Unit::Hour,
+        false,
+    ))
+}
+
+/// Convert an ffmpeg time duration into a systemd timespan, such as `01:30:00` into `1h 30min`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not an ffmpeg duration.
+pub fn to_systemd(input: &str) -> Result<String, error::Error> {
+    format::systemd(&parse(input)?)
+}
+
+/// Convert a systemd timespan into an ffmpeg time duration, such as `1h 30min` into `01:30:00`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a systemd duration, or cannot be represented by
+/// ffmpeg. See [`format()`].
+pub fn from_systemd(input: &str) -> Result<String, error::Error> {
+    format(&parser::parse_container(input)?)
+}
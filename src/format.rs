@@ -0,0 +1,451 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Formatting durations as systemd-style timespans.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::{duration::Container, format};
+//!
+//! let duration = Duration::from_millis(5_407_500);
+//! let formatted = format::timespan(&Container::from(duration), Duration::from_millis(1))
+//!     .expect("Could not format duration");
+//! assert_eq!(formatted, "1h 30min 7.5s");
+//! ```
+
+use crate::{
+    duration::{Container, Unit},
+    error,
+    locale::{self, Locale},
+};
+
+// The units systemd prints, largest first, along with the suffix it uses for each
+pub(crate) const UNITS: [(Unit, &str); 10] = [
+    (Unit::Year, "y"),
+    (Unit::Month, "month"),
+    (Unit::Week, "w"),
+    (Unit::Day, "d"),
+    (Unit::Hour, "h"),
+    (Unit::Minute, "min"),
+    (Unit::Second, "s"),
+    (Unit::Millisecond, "ms"),
+    (Unit::Microsecond, "us"),
+    (Unit::Nanosecond, "ns"),
+];
+
+// Shortest names for each unit, as used in the compact style
+const COMPACT_UNITS: [(Unit, &str); 10] = [
+    (Unit::Year, "y"),
+    (Unit::Month, "M"),
+    (Unit::Week, "w"),
+    (Unit::Day, "d"),
+    (Unit::Hour, "h"),
+    (Unit::Minute, "m"),
+    (Unit::Second, "s"),
+    (Unit::Millisecond, "ms"),
+    (Unit::Microsecond, "us"),
+    (Unit::Nanosecond, "ns"),
+];
+
+/// The layout of a formatted duration.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    /// Like systemd, e.g. `1h 30min 5s`
+    #[default]
+    Systemd,
+    /// Short units with no spaces, e.g. `1h30m5s`
+    Compact,
+    /// Full unit names, e.g. `1 hour, 30 minutes and 5 seconds`
+    Verbose,
+    /// Like a clock, e.g. `01:30:05`
+    Clock,
+}
+
+/// Options for formatting a duration.
+#[derive(Copy, Clone, Debug)]
+pub struct Options {
+    /// The layout to use
+    pub style: Style,
+    /// Round the duration to the nearest multiple of this. Zero is treated as one nanosecond.
+    pub accuracy: std::time::Duration,
+    /// Show at most this many parts, rounding to the smallest part shown. This has no effect on
+    /// the clock style.
+    pub max_parts: Option<usize>,
+    /// The largest unit to show. Anything larger is shown in this unit instead.
+    pub largest_unit: Unit,
+    /// The smallest unit to show. The duration is rounded to this unit.
+    pub smallest_unit: Unit,
+    /// The language of unit names in the verbose style
+    pub locale: &'static Locale,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            style: Style::Systemd,
+            accuracy: std::time::Duration::ZERO,
+            max_parts: None,
+            largest_unit: Unit::Year,
+            smallest_unit: Unit::Nanosecond,
+            locale: &locale::ENGLISH,
+        }
+    }
+}
+
+// Number of decimal digits needed for a fraction of the given number of nanoseconds
+const fn digits(mut nanos: u128) -> i32 {
+    let mut digits = 0;
+    while nanos > 1 {
+        nanos /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+// Round to the nearest multiple of the accuracy
+const fn round(nanos: u128, accuracy: u128) -> u128 {
+    nanos.saturating_add(accuracy / 2) / accuracy * accuracy
+}
+
+// The digits of a fraction of a unit, to the precision of the accuracy, without trailing zeros
+pub(crate) fn fraction(mut frac: u128, unit_nanos: u128, accuracy: u128) -> Option<(u128, usize)> {
+    let mut width = digits(unit_nanos) - digits(accuracy);
+    frac /= 10u128.pow(digits(accuracy).unsigned_abs());
+    while width > 0 && frac % 10 == 0 {
+        frac /= 10;
+        width -= 1;
+    }
+
+    (width > 0 && frac > 0).then(|| (frac, width.unsigned_abs() as usize))
+}
+
+// One part of a formatted duration, such as `30min` or `7.5s`
+struct Part {
+    unit: Unit,
+    whole: u128,
+    // The fractional digits, and how many of them there are
+    frac: Option<(u128, usize)>,
+}
+
+impl Part {
+    fn number(&self) -> String {
+        match self.frac {
+            Some((frac, width)) => format!("{}.{frac:0width$}", self.whole),
+            None => self.whole.to_string(),
+        }
+    }
+
+    // The number as a float, for choosing between singular and plural names
+    fn count(&self) -> f64 {
+        self.number().parse().unwrap_or(0.0)
+    }
+}
+
+// Split a rounded number of nanoseconds into parts, following systemd's format_timespan()
+fn split(mut t: u128, accuracy: u128, largest: Unit, smallest: Unit) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let units = UNITS
+        .iter()
+        .map(|&(unit, _)| unit)
+        .filter(|unit| unit.nanos() <= largest.nanos() && unit.nanos() >= smallest.nanos());
+    for unit in units {
+        if t == 0 {
+            break;
+        }
+
+        let unit_nanos = unit.nanos();
+        if t < unit_nanos {
+            continue;
+        }
+
+        let whole = t / unit_nanos;
+
+        // Under a minute, show the remainder as a fraction, to the precision of the accuracy
+        if t < Unit::Minute.nanos() {
+            if let Some(frac) = fraction(t % unit_nanos, unit_nanos, accuracy) {
+                parts.push(Part {
+                    unit,
+                    whole,
+                    frac: Some(frac),
+                });
+                break;
+            }
+        }
+
+        parts.push(Part {
+            unit,
+            whole,
+            frac: None,
+        });
+        t %= unit_nanos;
+    }
+
+    parts
+}
+
+// Look up the name of a unit in one of the tables
+fn name(table: &[(Unit, &'static str)], unit: Unit) -> &'static str {
+    table
+        .iter()
+        .find(|&&(u, _)| u == unit)
+        .map_or("", |&(_, name)| name)
+}
+
+// Systemd's name for a unit
+pub(crate) fn suffix(unit: Unit) -> &'static str {
+    name(&UNITS, unit)
+}
+
+// Format a duration like a clock, with hours, minutes, and seconds as needed. With `days`, whole
+// days are shown before the clock, as in `2d 04:00:00`.
+pub(crate) fn clock(sign: &str, mut t: u128, accuracy: u128, largest: Unit, days: bool) -> String {
+    let (day, hour, minute, second) = (
+        Unit::Day.nanos(),
+        Unit::Hour.nanos(),
+        Unit::Minute.nanos(),
+        Unit::Second.nanos(),
+    );
+
+    if days && t >= day {
+        let clock = clock("", t % day, accuracy, Unit::Hour, false);
+        t /= day;
+        return format!("{sign}{t}d {clock}");
+    }
+
+    let fields = if largest.nanos() >= hour {
+        vec![t / hour, t % hour / minute, t % minute / second]
+    } else if largest.nanos() >= minute {
+        vec![t / minute, t % minute / second]
+    } else {
+        vec![t / second]
+    };
+    let clock = fields
+        .iter()
+        .map(|field| format!("{field:02}"))
+        .collect::<Vec<_>>()
+        .join(":");
+
+    match fraction(t % second, second, accuracy) {
+        Some((frac, width)) => format!("{sign}{clock}.{frac:0width$}"),
+        None => format!("{sign}{clock}"),
+    }
+}
+
+// Split a number of nanoseconds into at most the given number of parts
+fn limited_split(
+    t: u128,
+    mut accuracy: u128,
+    largest: Unit,
+    smallest: Unit,
+    max_parts: Option<usize>,
+) -> Vec<Part> {
+    let mut parts = split(round(t, accuracy), accuracy, largest, smallest);
+    if let Some(max_parts) = max_parts.map(|max_parts| max_parts.max(1)) {
+        if parts.len() > max_parts {
+            // Round to the smallest part that will be shown, then split again
+            accuracy = accuracy.max(parts[max_parts - 1].unit.nanos());
+            parts = split(round(t, accuracy), accuracy, largest, smallest);
+            parts.truncate(max_parts);
+        }
+    }
+
+    parts
+}
+
+// Format a number of nanoseconds
+pub(crate) fn render(nanos: i128, options: &Options) -> String {
+    let sign = if nanos < 0 { "-" } else { "" };
+    let t = nanos.unsigned_abs();
+    let smallest = options.smallest_unit;
+    let largest = if options.largest_unit.nanos() < smallest.nanos() {
+        smallest
+    } else {
+        options.largest_unit
+    };
+    let accuracy = options.accuracy.as_nanos().max(smallest.nanos());
+    let parts = || limited_split(t, accuracy, largest, smallest, options.max_parts);
+
+    // Zero is shown in seconds, unless they're too small to show
+    let zero_unit = if smallest.nanos() > Unit::Second.nanos() {
+        smallest
+    } else {
+        Unit::Second
+    };
+
+    match options.style {
+        Style::Systemd => {
+            let parts: Vec<_> = parts()
+                .iter()
+                .map(|part| format!("{sign}{}{}", part.number(), name(&UNITS, part.unit)))
+                .collect();
+            if parts.is_empty() {
+                "0".to_owned()
+            } else {
+                parts.join(" ")
+            }
+        }
+        Style::Compact => {
+            let parts: Vec<_> = parts()
+                .iter()
+                .map(|part| format!("{sign}{}{}", part.number(), name(&COMPACT_UNITS, part.unit)))
+                .collect();
+            if parts.is_empty() {
+                format!("0{}", name(&COMPACT_UNITS, zero_unit))
+            } else {
+                parts.concat()
+            }
+        }
+        Style::Verbose => {
+            let mut parts: Vec<_> = parts()
+                .iter()
+                .map(|part| {
+                    let name = options.locale.name(part.unit, part.count());
+                    format!("{} {name}", part.number())
+                })
+                .collect();
+            let and = options.locale.and;
+            match parts.pop() {
+                None => format!("0 {}", options.locale.name(zero_unit, 0.0)),
+                Some(last) if parts.is_empty() => format!("{sign}{last}"),
+                Some(last) => format!("{sign}{} {and} {last}", parts.join(", ")),
+            }
+        }
+        Style::Clock => clock(sign, round(t, accuracy), accuracy, largest, false),
+    }
+}
+
+/// Format a duration as a systemd-style timespan, like systemd's `format_timespan()`.
+///
+/// The duration is rounded to the nearest multiple of `accuracy`, and split into the same units
+/// systemd prints, such as `1y 2month 3w 4d 5h 6min 7.5s`. Anything under a minute is shown as a
+/// decimal, to the precision of the accuracy. An accuracy of zero is treated as one nanosecond.
+///
+/// Negative durations have each part negated, so the result can be parsed again.
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the total duration is too large.
+pub fn timespan(
+    container: &Container,
+    accuracy: std::time::Duration,
+) -> Result<String, error::Error> {
+    with_options(
+        container,
+        &Options {
+            accuracy,
+            ..Options::default()
+        },
+    )
+}
+
+/// Format a duration with the given options.
+///
+/// The systemd and compact styles can be parsed again, as long as every unit is shown. The
+/// verbose style is intended for people to read, and the clock style is `HH:MM:SS`, with the
+/// hours including any larger units.
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the total duration is too large.
+pub fn with_options(container: &Container, options: &Options) -> Result<String, error::Error> {
+    Ok(render(container.nanos()?, options))
+}
+
+// Format a duration read from one of the other formats as a systemd timespan. Weeks are the
+// largest unit, as systemd's months and years are a little longer than this crate's, so
+// `1month` would be read as a different length.
+pub(crate) fn systemd(container: &Container) -> Result<String, error::Error> {
+    with_options(
+        container,
+        &Options {
+            largest_unit: Unit::Week,
+            ..Options::default()
+        },
+    )
+}
+
+/// A wrapper which formats a duration as a systemd-style timespan, created by
+/// [`SystemdExt::systemd`].
+///
+/// By default, the duration is formatted exactly, in the systemd style.
+#[derive(Copy, Clone, Debug)]
+pub struct SystemdDisplay {
+    nanos: i128,
+    options: Options,
+}
+
+impl SystemdDisplay {
+    /// Format the duration with the given options instead.
+    #[must_use]
+    pub const fn with_options(self, options: Options) -> Self {
+        Self { options, ..self }
+    }
+}
+
+impl std::fmt::Display for SystemdDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&render(self.nanos, &self.options))
+    }
+}
+
+/// Extension trait for formatting durations as systemd-style timespans.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use systemd_duration::format::SystemdExt;
+///
+/// let duration = Duration::from_secs(5_400);
+/// assert_eq!(duration.to_systemd_string(), "1h 30min");
+/// assert_eq!(format!("timeout {}", duration.systemd()), "timeout 1h 30min");
+/// ```
+pub trait SystemdExt {
+    /// Wrap the duration so it is displayed as a systemd-style timespan.
+    fn systemd(&self) -> SystemdDisplay;
+
+    /// Format the duration exactly as a systemd-style timespan.
+    fn to_systemd_string(&self) -> String {
+        self.systemd().to_string()
+    }
+}
+
+impl SystemdExt for std::time::Duration {
+    fn systemd(&self) -> SystemdDisplay {
+        // At most about 1.8e28, which fits easily
+        #[allow(clippy::cast_possible_wrap)]
+        let nanos = self.as_nanos() as i128;
+        SystemdDisplay {
+            nanos,
+            options: Options::default(),
+        }
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl SystemdExt for ::chrono::TimeDelta {
+    fn systemd(&self) -> SystemdDisplay {
+        SystemdDisplay {
+            nanos: i128::from(self.num_seconds()) * 1_000_000_000 + i128::from(self.subsec_nanos()),
+            options: Options::default(),
+        }
+    }
+}
+
+#[cfg(feature = "with-time")]
+impl SystemdExt for ::time::Duration {
+    fn systemd(&self) -> SystemdDisplay {
+        SystemdDisplay {
+            nanos: self.whole_nanoseconds(),
+            options: Options::default(),
+        }
+    }
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Go durations, as read by `time.ParseDuration` and written by `Duration.String()`.
+//!
+//! These look like systemd durations, such as `1h30m0.5s` or `-1.5h`, but differ in the details:
+//! the only units are `h`, `m`, `s`, `ms`, `us` (or `µs`) and `ns`, a sign is only allowed at the
+//! start, spaces aren't allowed, and every number needs a unit except a lone `0`. Durations are
+//! whole nanoseconds in an [`i64`], and are computed exactly as Go computes them.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::go;
+//!
+//! let container = go::parse("1h30m0.5s").expect("Could not parse duration");
+//! assert_eq!(Duration::try_from(container.clone()).unwrap(), Duration::from_millis(5_400_500));
+//! assert_eq!(go::format(&container).unwrap(), "1h30m0.5s");
+//! ```
+
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::{char, digit0, one_of},
+    combinator::{map, map_opt, opt, verify},
+    error::{ErrorKind::TooLarge, ParseError},
+    multi::many1,
+    sequence::preceded,
+    Err::Failure,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration},
+    error, format, parser,
+};
+
+// The units Go accepts, in nanoseconds
+const UNITS: [(&str, u64); 8] = [
+    ("ns", 1),
+    ("us", 1_000),
+    ("\u{b5}s", 1_000),
+    ("\u{3bc}s", 1_000),
+    ("ms", 1_000_000),
+    ("s", 1_000_000_000),
+    ("m", 60_000_000_000),
+    ("h", 3_600_000_000_000),
+];
+
+// Go keeps the magnitude in a u64, and allows it to reach 1 << 63 for the most negative duration
+const LIMIT: u64 = 1 << 63;
+
+// The digits before and after the point, and the unit in nanoseconds
+fn component<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, (&'a str, &'a str, u64), E> {
+    let (input, (whole, frac)) = verify(
+        (digit0, opt(preceded(char('.'), digit0))),
+        |&(whole, frac): &(&str, Option<&str>)| {
+            !whole.is_empty() || frac.is_some_and(|frac| !frac.is_empty())
+        },
+    )
+    .parse(input)?;
+    let (input, unit) = map_opt(
+        take_while1(|c: char| c != '.' && !c.is_ascii_digit()),
+        |name: &str| {
+            UNITS
+                .iter()
+                .find(|&&(u, _)| u == name)
+                .map(|&(_, nanos)| nanos)
+        },
+    )
+    .parse(input)?;
+
+    Ok((input, (whole, frac.unwrap_or(""), unit)))
+}
+
+// The whole part of a number, as Go's leadingInt reads it
+fn whole_part(digits: &str) -> Option<u64> {
+    digits.bytes().try_fold(0u64, |x, c| {
+        if x > LIMIT / 10 {
+            return None;
+        }
+        let x = x * 10 + u64::from(c - b'0');
+        (x <= LIMIT).then_some(x)
+    })
+}
+
+// The fraction of a number and its scale, as Go's leadingFraction reads it. Digits which would
+// overflow are ignored.
+fn fraction_part(digits: &str) -> (u64, f64) {
+    let (mut x, mut scale) = (0u64, 1.0);
+    for c in digits.bytes() {
+        if x > (LIMIT - 1) / 10 {
+            break;
+        }
+        let y = x * 10 + u64::from(c - b'0');
+        if y > LIMIT {
+            break;
+        }
+        x = y;
+        scale *= 10.0;
+    }
+    (x, scale)
+}
+
+// The length of a component in nanoseconds, as Go computes it
+fn component_nanos(whole: &str, frac: &str, unit: u64) -> Option<u64> {
+    let mut v = whole_part(whole)?;
+    if v > LIMIT / unit {
+        return None;
+    }
+    v *= unit;
+
+    let (f, scale) = fraction_part(frac);
+    if f > 0 {
+        // Go uses floating point here, to be accurate to the nanosecond for fractions of hours
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let extra = (f as f64 * (unit as f64 / scale)) as u64;
+        v += extra;
+        if v > LIMIT {
+            return None;
+        }
+    }
+
+    Some(v)
+}
+
+/// Parse a Go duration from the start of the input, returning the remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars. The result is a single
+/// nanosecond count.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a Go duration. Returns a [`Failure`] if the
+/// duration does not fit in an [`i64`] of nanoseconds.
+pub fn go<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let start = input;
+    let (input, sign) = opt(one_of("+-")).parse(input)?;
+    let (input, components) =
+        alt((many1(component), map(char('0'), |_| Vec::new()))).parse(input)?;
+
+    let overflow = || Failure(E::from_error_kind(start, TooLarge));
+    let total = components
+        .into_iter()
+        .try_fold(0u64, |total, (whole, frac, unit)| {
+            let total = total.checked_add(component_nanos(whole, frac, unit)?)?;
+            (total <= LIMIT).then_some(total)
+        })
+        .ok_or_else(overflow)?;
+
+    let nanos = if sign == Some('-') {
+        0i64.checked_sub_unsigned(total)
+    } else {
+        i64::try_from(total).ok()
+    }
+    .ok_or_else(overflow)?;
+
+    Ok((input, Container::new(vec![Duration::Nanosecond(nanos)])))
+}
+
+/// Parse an entire string as a Go duration, like Go's `time.ParseDuration`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a Go duration, or does not fit in an [`i64`] of
+/// nanoseconds. See [`go`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(go, input)
+}
+
+// A number of nanoseconds in the given unit, with the fraction trimmed of trailing zeros
+fn number(nanos: u64, unit: u64) -> String {
+    let (whole, frac) = (nanos / unit, nanos % unit);
+    match format::fraction(u128::from(frac), u128::from(unit), 1) {
+        Some((frac, width)) => format!("{whole}.{frac:0width$}"),
+        None => whole.to_string(),
+    }
+}
+
+/// Format a duration like Go's `Duration.String()`, such as `1h30m0.5s` or `300ms`.
+///
+/// Durations under a second use the largest of `ms`, `µs` and `ns` that fits. Longer durations
+/// always show seconds, and minutes and hours when they are needed. Zero is `0s`.
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the total duration does not fit in an [`i64`] of
+/// nanoseconds.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = i64::try_from(container.nanos()?).map_err(|_| error::Error::DurationOverflow)?;
+    let sign = if nanos < 0 { "-" } else { "" };
+    let u = nanos.unsigned_abs();
+
+    let (second, minute, hour) = (1_000_000_000, 60_000_000_000, 3_600_000_000_000);
+    let formatted = match u {
+        0 => "0s".to_owned(),
+        1..=999 => format!("{u}ns"),
+        1_000..=999_999 => format!("{}\u{b5}s", number(u, 1_000)),
+        1_000_000..=999_999_999 => format!("{}ms", number(u, 1_000_000)),
+        _ if u < minute => format!("{}s", number(u, second)),
+        _ if u < hour => format!("{}m{}s", u / minute, number(u % minute, second)),
+        _ => format!(
+            "{}h{}m{}s",
+            u / hour,
+            u % hour / minute,
+            number(u % minute, second)
+        ),
+    };
+
+    Ok(format!("{sign}{formatted}"))
+}
@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Durations as read by [`humantime::parse_duration`] and written by
+//! [`humantime::format_duration`], and how they compare to systemd's.
+//!
+//! humantime's syntax is close to systemd's, but not the same. It has extra unit names, such as
+//! `millis` and `nanos`, and lacks some of systemd's, such as `μs`. A month is 30.44 days and a
+//! year 365.25 days, where systemd uses 30.436875 and 365.2425 days. Durations can't be negative.
+//! [`compare`] shows whether a string means the same thing to both.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::humantime;
+//!
+//! assert!(humantime::compare("1h 30min").is_identical());
+//!
+//! // Months are slightly longer in humantime
+//! let compatibility = humantime::compare("1M");
+//! assert!(!compatibility.is_identical());
+//! assert_eq!(compatibility.difference(), Some(-270_000_000_000));
+//! ```
+
+use crate::{duration::Container, error, format, parser};
+
+/// How a string is parsed by this crate and by humantime, returned by [`compare`].
+#[derive(Debug)]
+pub struct Compatibility {
+    /// What this crate parses the string as, with [`parser::parse_container`]
+    pub systemd: Result<Container, error::Error>,
+    /// What humantime parses the string as, with [`humantime::parse_duration`]
+    pub humantime: Result<std::time::Duration, ::humantime::DurationError>,
+}
+
+impl Compatibility {
+    /// Whether both crates accept the string, and read it as the same length.
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.difference() == Some(0)
+    }
+
+    /// How many nanoseconds longer this crate reads the string than humantime, if both accept it.
+    #[must_use]
+    pub fn difference(&self) -> Option<i128> {
+        let systemd = self.systemd.as_ref().ok()?.nanos().ok()?;
+        let humantime = i128::try_from(self.humantime.as_ref().ok()?.as_nanos()).ok()?;
+        Some(systemd - humantime)
+    }
+}
+
+/// Parse a string with both this crate and humantime, to see whether they agree.
+#[must_use]
+pub fn compare(input: &str) -> Compatibility {
+    Compatibility {
+        systemd: parser::parse_container(input),
+        humantime: ::humantime::parse_duration(input),
+    }
+}
+
+/// Parse a string as humantime does.
+///
+/// # Errors
+///
+/// Returns [`error::Error::InvalidDuration`], with humantime's message, if humantime does not
+/// accept the input.
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    ::humantime::parse_duration(input)
+        .map(Container::from)
+        .map_err(|e| error::Error::InvalidDuration(e.to_string()))
+}
+
+/// Format a duration as humantime does, such as `1h 30m 500ms`.
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the duration is negative or too large for a
+/// [`std::time::Duration`].
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let duration = std::time::Duration::try_from(container.clone())?;
+    Ok(::humantime::format_duration(duration).to_string())
+}
+
+/// Convert a humantime duration into a systemd timespan, such as `2hrs` into `2h`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if humantime does not accept the input.
+pub fn to_systemd(input: &str) -> Result<String, error::Error> {
+    format::systemd(&parse(input)?)
+}
+
+/// Convert a systemd timespan into a humantime duration, such as `1.5h` into `1h 30m`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a systemd duration, or is negative. See
+/// [`format()`].
+pub fn from_systemd(input: &str) -> Result<String, error::Error> {
+    format(&parser::parse_container(input)?)
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! ISO 8601 durations, such as `P1Y2M3DT4H5M6.5S` or `PT90M`.
+//!
+//! Years, months, weeks and days come before the `T`, and hours, minutes and seconds after it, so
+//! `P1M` is a month and `PT1M` is a minute. Like the systemd parser, any component may have a
+//! fraction and a sign, and years and months have the same lengths. A sign before the `P` applies
+//! to the whole duration. Fractions may use a comma, as ISO 8601 prefers.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::{duration::Container, iso8601};
+//!
+//! let container = iso8601::parse("PT1H30M").expect("Could not parse duration");
+//! assert_eq!(Duration::try_from(container).unwrap(), Duration::from_secs(5_400));
+//!
+//! let container = Container::from(Duration::from_millis(93_784_500));
+//! assert_eq!(iso8601::format(&container).unwrap(), "P1DT2H3M4.5S");
+//! ```
+
+use nom::{
+    character::complete::{char, digit1, multispace0, one_of},
+    combinator::{map, opt, recognize},
+    error::{ErrorKind::Verify, ParseError},
+    sequence::{delimited, preceded, terminated},
+    Err::Error,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, format, parser,
+};
+
+// A signed decimal number, with either a full stop or a comma before the fraction
+fn number<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, f64, E> {
+    map(
+        recognize((opt(one_of("+-")), digit1, opt((one_of(".,"), digit1)))),
+        |s: &str| s.replace(',', ".").parse::<f64>().unwrap(),
+    )
+    .parse(input)
+}
+
+// A number followed by the letter for its unit, such as `3D`
+fn component<'a, E: ParseError<&'a str>>(
+    designator: char,
+) -> impl Parser<&'a str, Output = f64, Error = E> {
+    terminated(number, char(designator))
+}
+
+/// Parse an ISO 8601 duration from the start of the input, returning the remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with an ISO 8601 duration, or it has no
+/// components, or a `T` with no time components after it.
+pub fn iso8601<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let (input, sign) = opt(one_of("+-")).parse(input)?;
+    let (input, _) = char('P').parse(input)?;
+    let (input, (years, months, weeks, days)) = (
+        opt(component('Y')),
+        opt(component('M')),
+        opt(component('W')),
+        opt(component('D')),
+    )
+        .parse(input)?;
+    let (input, time) = opt(preceded(
+        char('T'),
+        (
+            opt(component('H')),
+            opt(component('M')),
+            opt(component('S')),
+        ),
+    ))
+    .parse(input)?;
+
+    let (hours, minutes, seconds) = time.unwrap_or_default();
+    let durations: Vec<_> = [
+        (Unit::Year, years),
+        (Unit::Month, months),
+        (Unit::Week, weeks),
+        (Unit::Day, days),
+        (Unit::Hour, hours),
+        (Unit::Minute, minutes),
+        (Unit::Second, seconds),
+    ]
+    .into_iter()
+    .filter_map(|(unit, count)| {
+        let count = if sign == Some('-') { -count? } else { count? };
+        Duration::from_count(unit, count)
+    })
+    .collect();
+
+    let empty_time = time.is_some_and(|(h, m, s)| h.is_none() && m.is_none() && s.is_none());
+    if durations.is_empty() || empty_time {
+        return Err(Error(E::from_error_kind(input, Verify)));
+    }
+
+    Ok((input, Container::new(durations)))
+}
+
+/// Parse an entire string as an ISO 8601 duration, ignoring surrounding whitespace.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not an ISO 8601 duration. See [`iso8601`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(delimited(multispace0, iso8601, multispace0), input)
+}
+
+/// Format a duration in ISO 8601, such as `P1DT2H3M4.5S`.
+///
+/// Only days, hours, minutes and seconds are used, as they have exact lengths. The fraction of a
+/// second is shown to the nanosecond, without trailing zeros. Negative durations have a sign
+/// before the `P`, and zero is `PT0S`.
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the total duration is too large.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = container.nanos()?;
+    let mut t = nanos.unsigned_abs();
+    let (day, hour, minute, second) = (
+        Unit::Day.nanos(),
+        Unit::Hour.nanos(),
+        Unit::Minute.nanos(),
+        Unit::Second.nanos(),
+    );
+
+    let mut out = vec![String::from(if nanos < 0 { "-P" } else { "P" })];
+    if t >= day {
+        out.push(format!("{}D", t / day));
+        t %= day;
+    }
+    if t > 0 || nanos == 0 {
+        out.push("T".to_owned());
+        for (size, designator) in [(hour, 'H'), (minute, 'M')] {
+            if t >= size {
+                out.push(format!("{}{designator}", t / size));
+                t %= size;
+            }
+        }
+        if t > 0 || nanos == 0 {
+            out.push(match format::fraction(t % second, second, 1) {
+                Some((frac, width)) => format!("{}.{frac:0width$}S", t / second),
+                None => format!("{}S", t / second),
+            });
+        }
+    }
+
+    Ok(out.concat())
+}
@@ -15,6 +15,8 @@
 //! * [`time::Duration`][::time::Duration] (with the `with-time` feature)
 //! * [`chrono::TimeDelta`][::chrono::TimeDelta] (with the `with-chrono` feature)
 //! * [`std::time::Duration`]
+//! * [`prost_types::Duration`] (with the `with-prost` feature)
+//! * [`humantime::Duration`][::humantime::Duration] (with the `with-humantime` feature)
 //!
 //! It uses the [`nom`] library to parse durations.
 //!
@@ -30,14 +32,35 @@
 #![warn(clippy::nursery)]
 #![warn(clippy::pedantic)]
 
+pub mod approx;
 #[cfg(feature = "with-chrono")]
 pub mod chrono;
+pub mod clock;
+pub mod completion;
+pub mod coreutils;
+pub mod dialect;
+pub mod dotnet;
 pub mod duration;
 pub mod error;
+pub mod eventbridge;
+pub mod ffmpeg;
+pub mod format;
+pub mod go;
+#[cfg(feature = "with-humantime")]
+pub mod humantime;
+pub mod iso8601;
+pub mod locale;
+pub mod natural;
 pub mod parser;
+pub mod postgres;
+pub mod prometheus;
+#[cfg(feature = "with-prost")]
+pub mod protobuf;
+pub mod scan;
 pub mod stdtime;
 #[cfg(feature = "with-time")]
 pub mod time;
+pub mod token;
 
 #[cfg(test)]
 mod tests {
@@ -521,6 +544,1241 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stdtime_duration_parse_bytes() {
+        use std::{ffi::OsStr, time};
+
+        let duration_compare = time::Duration::from_micros(90_000_003);
+
+        if let Ok(duration) = parser::stdtime::parse_bytes(b"1min 30s 3us") {
+            assert_eq!(duration_compare, duration);
+        } else {
+            panic!("Parse failure");
+        }
+
+        if let Ok(duration) = parser::stdtime::parse_bytes("1min 30s 3µs".as_bytes()) {
+            assert_eq!(duration_compare, duration);
+        } else {
+            panic!("Parse failure");
+        }
+
+        if let Ok(duration) = parser::stdtime::parse_os_str(OsStr::new("1min 30s 3μs")) {
+            assert_eq!(duration_compare, duration);
+        } else {
+            panic!("Parse failure");
+        }
+    }
+
+    #[test]
+    fn test_stdtime_duration_parse_bytes_invalid() {
+        assert!(matches!(
+            parser::stdtime::parse_bytes(b"30s\xff"),
+            Err(error::Error::InvalidByte {
+                byte: 0xff,
+                offset: 3
+            })
+        ));
+
+        assert!(matches!(
+            parser::stdtime::parse_bytes("1 \u{e9}s".as_bytes()),
+            Err(error::Error::InvalidByte {
+                byte: 0xc3,
+                offset: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_duration_embedded() {
+        use nom::{bytes::complete::tag, character::complete::char, sequence::delimited, Parser};
+        use std::time;
+
+        let duration_compare = time::Duration::from_secs(5_400);
+
+        let mut timeout = delimited(
+            tag("timeout="),
+            parser::duration::<nom::error::Error<&str>>,
+            char(';'),
+        );
+        if let Ok((rest, container)) = timeout.parse("timeout=1h30m; retries=3") {
+            assert_eq!(rest, " retries=3");
+            assert_eq!(
+                duration_compare,
+                time::Duration::try_from(container).unwrap()
+            );
+        } else {
+            panic!("Parse failure");
+        }
+
+        if let Ok((rest, container)) = parser::duration::<()>("5400;") {
+            assert_eq!(rest, ";");
+            assert_eq!(
+                duration_compare,
+                time::Duration::try_from(container).unwrap()
+            );
+        } else {
+            panic!("Parse failure");
+        }
+
+        if let Ok((rest, _)) = parser::duration_fragment::<()>(" 1h 30m") {
+            assert_eq!(rest, " 30m");
+        } else {
+            panic!("Parse failure");
+        }
+
+        assert!(parser::duration::<()>("timeout").is_err());
+    }
+
+    #[test]
+    fn test_scan() {
+        use std::time;
+
+        let text = "restart took 1min 3.5s, retrying in 30s (attempt 2 of 5mph)";
+        let found: Vec<_> = scan::scan(text).collect();
+        assert_eq!(found.len(), 2);
+
+        assert_eq!(found[0].as_str(), "1min 3.5s");
+        assert_eq!(found[0].span(), 13..22);
+        assert_eq!(
+            time::Duration::from_millis(63_500),
+            time::Duration::try_from(found[0].clone().into_container()).unwrap()
+        );
+
+        assert_eq!(found[1].as_str(), "30s");
+        assert_eq!(found[1].span(), 36..39);
+
+        assert_eq!(scan::scan("abc5s 10 mice v1.5s").count(), 0);
+
+        // Compact durations with no space between the fragments
+        let found: Vec<_> = scan::scan("retry in 1h30m now, took 2min3s").collect();
+        assert_eq!(
+            found.iter().map(scan::Match::as_str).collect::<Vec<_>>(),
+            ["1h30m", "2min3s"]
+        );
+        assert_eq!(
+            time::Duration::try_from(found[0].clone().into_container()).unwrap(),
+            time::Duration::from_secs(5_400)
+        );
+        assert_eq!(
+            time::Duration::try_from(found[1].clone().into_container()).unwrap(),
+            time::Duration::from_secs(123)
+        );
+
+        // Fragments which run into a word are backed off from
+        let found: Vec<_> = scan::scan("in 1h 30m5x, 2s5mph")
+            .map(|m| m.as_str())
+            .collect();
+        assert_eq!(found, ["1h"]);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        use duration::Unit;
+        use token::TokenKind;
+
+        let tokens: Vec<_> = token::tokenize("-1.5 hours 2.x%µs")
+            .map(|t| (t.kind, t.span))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (TokenKind::Sign, 0..1),
+                (TokenKind::Number, 1..4),
+                (TokenKind::Whitespace, 4..5),
+                (TokenKind::Unit(Unit::Hour), 5..10),
+                (TokenKind::Whitespace, 10..11),
+                (TokenKind::Number, 11..12),
+                (TokenKind::Invalid, 12..13),
+                (TokenKind::Invalid, 13..14),
+                (TokenKind::Invalid, 14..15),
+                (TokenKind::Unit(Unit::Microsecond), 15..18),
+            ]
+        );
+
+        assert_eq!(token::tokenize("").count(), 0);
+    }
+
+    #[test]
+    fn test_complete() {
+        use completion::{complete, Completion};
+        use duration::Unit;
+
+        let completions = complete("15mi", 4);
+        assert_eq!(
+            completions[..4],
+            [
+                Completion::Unit {
+                    name: "min",
+                    unit: Unit::Minute,
+                    replace: 2..4
+                },
+                Completion::Unit {
+                    name: "mins",
+                    unit: Unit::Minute,
+                    replace: 2..4
+                },
+                Completion::Unit {
+                    name: "minute",
+                    unit: Unit::Minute,
+                    replace: 2..4
+                },
+                Completion::Unit {
+                    name: "minutes",
+                    unit: Unit::Minute,
+                    replace: 2..4
+                },
+            ]
+        );
+        assert!(completions.iter().all(|c| matches!(
+            c,
+            Completion::Unit { name, .. } if name.starts_with("mi")
+        )));
+
+        assert_eq!(complete("", 0), [Completion::Number]);
+        assert_eq!(complete("1h ", 3), [Completion::Number]);
+        assert_eq!(complete("1h -", 4), [Completion::Number]);
+        assert_eq!(complete("1h", 2).last(), Some(&Completion::Number));
+        assert_eq!(complete("15 ", 3).len(), parser::UNITS.len());
+        assert!(complete("15 x", 4).is_empty());
+        assert!(complete("mi", 2).is_empty());
+
+        // The rest of the word after the cursor is replaced too
+        assert!(matches!(
+            complete("15hox", 3)[..],
+            [
+                Completion::Unit {
+                    replace: std::ops::Range { start: 2, end: 5 },
+                    ..
+                },
+                ..
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_format_timespan() {
+        use duration::Container;
+        use std::time;
+
+        let accuracy = time::Duration::from_millis(1);
+        let input = "1y 2month 3w 4d 5h 6min 7.5s";
+        let duration = parser::stdtime::parse(input).unwrap();
+        assert_eq!(
+            format::timespan(&Container::from(duration), accuracy).unwrap(),
+            input
+        );
+
+        let duration = time::Duration::new(3_723, 456_789_012);
+        let formatted = format::timespan(&Container::from(duration), time::Duration::ZERO).unwrap();
+        assert_eq!(formatted, "1h 2min 3.456789012s");
+        assert_eq!(parser::stdtime::parse(&formatted).unwrap(), duration);
+
+        let formatted =
+            format::timespan(&Container::from(duration), time::Duration::from_secs(1)).unwrap();
+        assert_eq!(formatted, "1h 2min 3s");
+
+        let formatted = format::timespan(&Container::from(duration), accuracy).unwrap();
+        assert_eq!(formatted, "1h 2min 3.457s");
+
+        let duration = time::Duration::from_nanos(1_500);
+        let formatted = format::timespan(&Container::from(duration), time::Duration::ZERO);
+        assert_eq!(formatted.unwrap(), "1.5us");
+
+        let formatted = format::timespan(&Container::from(time::Duration::ZERO), accuracy).unwrap();
+        assert_eq!(formatted, "0");
+
+        let delta = ::chrono::TimeDelta::seconds(-90);
+        let formatted = format::timespan(&Container::from(delta), accuracy).unwrap();
+        assert_eq!(formatted, "-1min -30s");
+        assert_eq!(parser::chrono::parse(&formatted).unwrap(), delta);
+
+        let duration = ::time::Duration::days(400);
+        let formatted = format::timespan(&Container::from(duration), accuracy).unwrap();
+        assert_eq!(formatted, "1y 1month 4d 7h 41min 42s");
+        assert_eq!(parser::time::parse(&formatted).unwrap(), duration);
+    }
+
+    #[test]
+    fn test_format_styles() {
+        use duration::{Container, Unit};
+        use format::{Options, Style};
+        use std::time;
+
+        let container = Container::from(time::Duration::from_millis(5_405_250));
+        let format = |options: Options| format::with_options(&container, &options).unwrap();
+
+        assert_eq!(format(Options::default()), "1h 30min 5.25s");
+        assert_eq!(
+            format(Options {
+                style: Style::Compact,
+                ..Options::default()
+            }),
+            "1h30m5.25s"
+        );
+        assert_eq!(
+            format(Options {
+                style: Style::Verbose,
+                smallest_unit: Unit::Second,
+                ..Options::default()
+            }),
+            "1 hour, 30 minutes and 5 seconds"
+        );
+        assert_eq!(
+            format(Options {
+                style: Style::Clock,
+                ..Options::default()
+            }),
+            "01:30:05.25"
+        );
+        assert_eq!(
+            format(Options {
+                style: Style::Clock,
+                accuracy: time::Duration::from_secs(1),
+                ..Options::default()
+            }),
+            "01:30:05"
+        );
+        assert_eq!(
+            format(Options {
+                style: Style::Compact,
+                largest_unit: Unit::Minute,
+                ..Options::default()
+            }),
+            "90m5.25s"
+        );
+        assert_eq!(
+            format(Options {
+                style: Style::Verbose,
+                max_parts: Some(1),
+                ..Options::default()
+            }),
+            "2 hours"
+        );
+        assert_eq!(
+            format(Options {
+                max_parts: Some(2),
+                ..Options::default()
+            }),
+            "1h 30min"
+        );
+
+        let container = Container::from(time::Duration::ZERO);
+        assert_eq!(
+            format::with_options(
+                &container,
+                &Options {
+                    style: Style::Verbose,
+                    ..Options::default()
+                }
+            )
+            .unwrap(),
+            "0 seconds"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        use duration::{Container, Duration};
+
+        assert_eq!(Duration::Minute(30.0).to_string(), "30min");
+        assert_eq!(Duration::Second(-1.5).to_string(), "-1.5s");
+        assert_eq!(Duration::Nanosecond(10).to_string(), "10ns");
+
+        let container = Container::new(vec![Duration::Hour(1.0), Duration::Minute(30.0)]);
+        assert_eq!(container.to_string(), "1h 30min");
+        assert_eq!(format!("{container:#}"), "1h 30min");
+        assert_eq!(Container::new(Vec::new()).to_string(), "0");
+
+        let (_, container) = parser::duration::<()>("1 hours 30m 2.5 µs").unwrap();
+        assert_eq!(container.to_string(), "1h 30min 2.5us");
+        assert_eq!(format!("{container:#}"), "1hours 30m 2.5µs");
+
+        let (_, container) = parser::duration::<()>("90").unwrap();
+        assert_eq!(container.to_string(), "90s");
+        assert_eq!(format!("{container:#}"), "90");
+    }
+
+    #[test]
+    fn test_systemd_ext() {
+        use format::{Options, Style, SystemdExt};
+        use std::time;
+
+        let duration = time::Duration::from_millis(5_405_250);
+        assert_eq!(duration.to_systemd_string(), "1h 30min 5.25s");
+        assert_eq!(format!("{}", duration.systemd()), "1h 30min 5.25s");
+
+        let compact = Options {
+            style: Style::Compact,
+            accuracy: time::Duration::from_secs(1),
+            ..Options::default()
+        };
+        assert_eq!(
+            duration.systemd().with_options(compact).to_string(),
+            "1h30m5s"
+        );
+
+        let delta = ::chrono::TimeDelta::milliseconds(-1_500);
+        assert_eq!(delta.to_systemd_string(), "-1.5s");
+        assert_eq!(
+            parser::chrono::parse(&delta.to_systemd_string()).unwrap(),
+            delta
+        );
+
+        let duration = ::time::Duration::new(86_400, 1);
+        assert_eq!(duration.to_systemd_string(), "1d 1ns");
+        assert_eq!(
+            parser::time::parse(&duration.to_systemd_string()).unwrap(),
+            duration
+        );
+    }
+
+    #[test]
+    fn test_approx() {
+        use approx::{Options, Rounding, Tense};
+        use duration::{Container, Unit};
+
+        let approx = |secs: i64, options: Options| {
+            approx::format(
+                &Container::from(::chrono::TimeDelta::seconds(secs)),
+                &options,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(approx(6_900, Options::default()), "about 2 hours");
+        assert_eq!(approx(7_200, Options::default()), "2 hours");
+        assert_eq!(approx(3_300, Options::default()), "about an hour");
+        assert_eq!(approx(3_000, Options::default()), "50 minutes");
+        assert_eq!(approx(90_000, Options::default()), "just over a day");
+        assert_eq!(approx(30, Options::default()), "less than a minute");
+        assert_eq!(approx(0, Options::default()), "less than a minute");
+        assert_eq!(approx(1_209_600, Options::default()), "2 weeks");
+
+        // Thresholds outside the range are treated as 1
+        for threshold in [0.0, -1.0, 2.0, f64::NAN] {
+            let options = Options {
+                threshold,
+                ..Options::default()
+            };
+            assert_eq!(approx(300, options), "5 minutes", "{threshold}");
+            assert_eq!(approx(3_300, options), "55 minutes", "{threshold}");
+        }
+
+        let future = Options {
+            tense: Tense::Auto,
+            ..Options::default()
+        };
+        assert_eq!(approx(290, future), "in about 5 minutes");
+        assert_eq!(approx(-290, future), "about 5 minutes ago");
+        assert_eq!(
+            approx(
+                290,
+                Options {
+                    tense: Tense::Past,
+                    ..Options::default()
+                }
+            ),
+            "about 5 minutes ago"
+        );
+
+        let down = Options {
+            rounding: Rounding::Down,
+            ..Options::default()
+        };
+        assert_eq!(approx(6_600, down), "over an hour");
+        let up = Options {
+            rounding: Rounding::Up,
+            ..Options::default()
+        };
+        assert_eq!(approx(4_200, up), "under 2 hours");
+
+        let seconds = Options {
+            smallest_unit: Unit::Second,
+            threshold: 1.0,
+            ..Options::default()
+        };
+        assert_eq!(approx(59, seconds), "59 seconds");
+        assert_eq!(approx(3_300, seconds), "55 minutes");
+    }
+
+    #[test]
+    fn test_locale() {
+        use std::time;
+
+        use crate::{
+            duration::{Container, Unit},
+            format::{self, Style},
+            parser::Options,
+        };
+
+        let german = Options {
+            locale: Some(&locale::GERMAN),
+            ..Options::default()
+        };
+        assert_eq!(
+            stdtime::parse_with("2 Stunden 30 Minuten", &german).unwrap(),
+            time::Duration::from_secs(9_000)
+        );
+        assert_eq!(
+            stdtime::parse_with("1 Tag 5min", &german).unwrap(),
+            time::Duration::from_secs(86_700)
+        );
+        assert!(stdtime::parse_with("2 heures", &german).is_err());
+        assert!(stdtime::parse("2 Stunden").is_err());
+
+        let spanish = Options {
+            locale: locale::from_tag("es-MX"),
+            ..Options::default()
+        };
+        assert_eq!(
+            stdtime::parse_with("3 días 1 hora", &spanish).unwrap(),
+            time::Duration::from_secs(262_800)
+        );
+
+        let format = |locale| {
+            format::with_options(
+                &Container::from(time::Duration::from_secs(5_400)),
+                &format::Options {
+                    style: Style::Verbose,
+                    locale,
+                    ..format::Options::default()
+                },
+            )
+            .unwrap()
+        };
+        assert_eq!(format(&locale::ENGLISH), "1 hour and 30 minutes");
+        assert_eq!(format(&locale::GERMAN), "1 Stunde und 30 Minuten");
+        assert_eq!(format(&locale::FRENCH), "1 heure et 30 minutes");
+        assert_eq!(format(&locale::PORTUGUESE), "1 hora e 30 minutos");
+        assert_eq!(locale::FRENCH.name(Unit::Hour, 1.5), "heure");
+    }
+
+    #[test]
+    fn test_natural() {
+        use std::time;
+
+        let parse = |input| time::Duration::try_from(natural::parse(input).unwrap()).unwrap();
+
+        assert_eq!(
+            parse("an hour and a half"),
+            time::Duration::from_secs(5_400)
+        );
+        assert_eq!(parse("half a day"), time::Duration::from_secs(43_200));
+        assert_eq!(parse("a half hour"), time::Duration::from_secs(1_800));
+        assert_eq!(
+            parse("2 hours and 15 minutes"),
+            time::Duration::from_secs(8_100)
+        );
+        assert_eq!(parse("a couple of seconds"), time::Duration::from_secs(2));
+        assert_eq!(
+            parse("One and a half Days"),
+            time::Duration::from_secs(129_600)
+        );
+        assert_eq!(
+            parse("twenty-five minutes, 3 seconds and 500 ms"),
+            time::Duration::from_millis(1_503_500)
+        );
+        assert_eq!(parse("1 hour 30min"), time::Duration::from_secs(5_400));
+
+        for (input, offset) in [
+            ("a few minutes", 2),
+            ("an hour and a half minutes", 19),
+            ("2 hours and", 11),
+            ("twenty-zero seconds", 0),
+            ("", 0),
+        ] {
+            if let Err(error::Error::InvalidPhrase { offset: found }) = natural::parse(input) {
+                assert_eq!(found, offset, "{input}");
+            } else {
+                panic!("Parsed {input}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        use std::time;
+
+        use crate::parser::{Case, Collision, Options};
+
+        let options = Options {
+            case: Case::Insensitive(Collision::ExactCase),
+            ..Options::default()
+        };
+        assert_eq!(
+            stdtime::parse_with("1H 30MIN 5Sec", &options).unwrap(),
+            time::Duration::from_secs(5_405)
+        );
+        assert_eq!(
+            stdtime::parse_with("2m", &options).unwrap(),
+            time::Duration::from_secs(120)
+        );
+        assert_eq!(
+            stdtime::parse_with("1M", &options).unwrap(),
+            time::Duration::from_secs(2_629_746)
+        );
+        assert!(stdtime::parse("1H").is_err());
+
+        let options = Options {
+            case: Case::Insensitive(Collision::Reject),
+            ..Options::default()
+        };
+        assert_eq!(
+            stdtime::parse_with("2MINS", &options).unwrap(),
+            time::Duration::from_secs(120)
+        );
+        for input in ["2m", "1M", "1h 2m"] {
+            if let Err(error::Error::AmbiguousUnit(name)) = stdtime::parse_with(input, &options) {
+                assert_eq!(name, input[input.len() - 1..]);
+            } else {
+                panic!("Parsed {input}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_clock() {
+        use std::time;
+
+        let parse = |input| time::Duration::try_from(clock::parse(input).unwrap()).unwrap();
+
+        assert_eq!(parse("01:30:00"), time::Duration::from_secs(5_400));
+        assert_eq!(parse("1:02:03.250"), time::Duration::from_millis(3_723_250));
+        assert_eq!(parse("2d 04:00:00"), time::Duration::from_secs(187_200));
+        assert_eq!(parse("3 days 00:00:01"), time::Duration::from_secs(259_201));
+        assert_eq!(parse("05:07.5"), time::Duration::from_millis(307_500));
+        assert_eq!(parse("36:00:00"), time::Duration::from_secs(129_600));
+        assert_eq!(
+            ::time::Duration::try_from(clock::parse("-00:00:01.5").unwrap()).unwrap(),
+            ::time::Duration::milliseconds(-1_500)
+        );
+
+        for input in [
+            "1:60:00",
+            "1:2:03",
+            "2d 24:00:00",
+            "2d 05:00",
+            "01:30:00x",
+            "",
+        ] {
+            assert!(clock::parse(input).is_err(), "{input}");
+        }
+
+        let format = |input| clock::format(&clock::parse(input).unwrap()).unwrap();
+        assert_eq!(format("1:02:03.250"), "01:02:03.25");
+        assert_eq!(format("36:00:00"), "1d 12:00:00");
+        assert_eq!(format("-2d 04:00:00"), "-2d 04:00:00");
+        assert_eq!(format("00:00"), "00:00:00");
+    }
+
+    #[test]
+    fn test_iso8601() {
+        use std::time;
+
+        let parse = |input| time::Duration::try_from(iso8601::parse(input).unwrap()).unwrap();
+
+        assert_eq!(parse("PT90M"), time::Duration::from_secs(5_400));
+        assert_eq!(parse("P1M"), time::Duration::from_secs(2_629_746));
+        assert_eq!(parse("P2W"), time::Duration::from_secs(1_209_600));
+        assert_eq!(
+            parse("P1Y2M3DT4H5M6.5S"),
+            time::Duration::from_millis(37_090_350_500)
+        );
+        assert_eq!(parse("PT0,25S"), time::Duration::from_millis(250));
+        assert_eq!(
+            ::time::Duration::try_from(iso8601::parse("PT1H-30M").unwrap()).unwrap(),
+            ::time::Duration::minutes(30)
+        );
+
+        if let Ok(duration) = time::Duration::try_from(iso8601::parse("-PT5S").unwrap()) {
+            panic!("Negative duration converted: {duration:?}");
+        }
+        if let Ok(duration) = ::time::Duration::try_from(iso8601::parse("-PT5S").unwrap()) {
+            assert_eq!(duration, ::time::Duration::seconds(-5));
+        } else {
+            panic!("Parse failure");
+        }
+
+        for input in ["P", "PT", "P1H", "PT1D", "P1S", "1D", "PT1M1H", "P1DT"] {
+            assert!(iso8601::parse(input).is_err(), "{input}");
+        }
+
+        let format = |duration| iso8601::format(&duration::Container::from(duration)).unwrap();
+        assert_eq!(format(time::Duration::ZERO), "PT0S");
+        assert_eq!(format(time::Duration::from_secs(5_400)), "PT1H30M");
+        assert_eq!(format(time::Duration::from_secs(172_800)), "P2D");
+        assert_eq!(format(time::Duration::from_nanos(1_500)), "PT0.0000015S");
+        assert_eq!(
+            iso8601::format(&iso8601::parse("-P1DT0.5S").unwrap()).unwrap(),
+            "-P1DT0.5S"
+        );
+    }
+
+    #[test]
+    fn test_go() {
+        use std::time;
+
+        let nanos = |input| {
+            let container = go::parse(input).unwrap();
+            ::time::Duration::try_from(container)
+                .unwrap()
+                .whole_nanoseconds()
+        };
+
+        assert_eq!(nanos("1h30m0.5s"), 5_400_500_000_000);
+        assert_eq!(nanos("300ms"), 300_000_000);
+        assert_eq!(nanos("-1.5h"), -5_400_000_000_000);
+        assert_eq!(nanos("2h45m"), 9_900_000_000_000);
+        assert_eq!(nanos("1\u{b5}s1us1\u{3bc}s1ns"), 3_001);
+        assert_eq!(nanos("0"), 0);
+        assert_eq!(nanos("-0"), 0);
+        assert_eq!(nanos("1.s"), 1_000_000_000);
+        assert_eq!(nanos(".5m"), 30_000_000_000);
+        assert_eq!(nanos("0.3333333333333333333h"), 1_200_000_000_000);
+        assert_eq!(nanos("9223372036854775807ns"), i128::from(i64::MAX));
+        assert_eq!(nanos("-9223372036854775808ns"), i128::from(i64::MIN));
+
+        for input in [
+            "",
+            "1",
+            "1d",
+            "1h 30m",
+            "1h-30m",
+            "+-1s",
+            ".s",
+            "9223372036854775808ns",
+            "2562048h",
+        ] {
+            assert!(go::parse(input).is_err(), "{input}");
+        }
+
+        let format = |duration| go::format(&duration::Container::from(duration)).unwrap();
+        assert_eq!(format(time::Duration::ZERO), "0s");
+        assert_eq!(format(time::Duration::from_nanos(1)), "1ns");
+        assert_eq!(format(time::Duration::from_nanos(1_100)), "1.1\u{b5}s");
+        assert_eq!(format(time::Duration::from_millis(300)), "300ms");
+        assert_eq!(format(time::Duration::from_millis(61_500)), "1m1.5s");
+        assert_eq!(format(time::Duration::from_secs(3_600)), "1h0m0s");
+        assert_eq!(
+            go::format(&go::parse("-9223372036854775808ns").unwrap()).unwrap(),
+            "-2562047h47m16.854775808s"
+        );
+    }
+
+    #[test]
+    fn test_prometheus() {
+        use std::time;
+
+        let parse = |input| time::Duration::try_from(prometheus::parse(input).unwrap()).unwrap();
+
+        assert_eq!(parse("5m"), time::Duration::from_secs(300));
+        assert_eq!(parse("1h30m"), time::Duration::from_secs(5_400));
+        assert_eq!(parse("2w"), time::Duration::from_secs(1_209_600));
+        assert_eq!(parse("1y"), time::Duration::from_secs(31_536_000));
+        assert_eq!(parse("1m500ms"), time::Duration::from_millis(60_500));
+        assert_eq!(parse("0"), time::Duration::ZERO);
+
+        for input in [
+            "", "1.5h", "30m1h", "1h1h", "-5m", "1h 30m", "5min", "1d1M", "106752d",
+        ] {
+            assert!(prometheus::parse(input).is_err(), "{input}");
+        }
+
+        let format = |duration| prometheus::format(&duration::Container::from(duration));
+        assert_eq!(format(time::Duration::ZERO).unwrap(), "0s");
+        assert_eq!(format(time::Duration::from_secs(5_400)).unwrap(), "1h30m");
+        assert_eq!(format(time::Duration::from_secs(7_776_000)).unwrap(), "90d");
+        assert_eq!(format(time::Duration::from_secs(31_536_000)).unwrap(), "1y");
+        assert_eq!(
+            format(time::Duration::from_millis(1_500)).unwrap(),
+            "1s500ms"
+        );
+        assert!(matches!(
+            format(time::Duration::from_micros(1_500)),
+            Err(error::Error::Unrepresentable(_))
+        ));
+
+        assert_eq!(prometheus::to_systemd("1h30m").unwrap(), "1h 30min");
+        // systemd's years and months are longer than this crate's, so weeks are the largest unit
+        assert_eq!(prometheus::to_systemd("1y").unwrap(), "52w 1d");
+        assert_eq!(prometheus::from_systemd("1min 30s").unwrap(), "1m30s");
+        assert_eq!(prometheus::from_systemd("1y").unwrap(), "365d5h49m12s");
+    }
+
+    #[test]
+    fn test_postgres() {
+        use std::time;
+
+        let parse = |input| time::Duration::try_from(postgres::parse(input).unwrap()).unwrap();
+
+        assert_eq!(
+            parse("1 year 2 mons 3 days 04:05:06.789"),
+            time::Duration::from_millis(37_015_506_789)
+        );
+        assert_eq!(parse("3 days"), time::Duration::from_secs(259_200));
+        assert_eq!(parse("100:00:00"), time::Duration::from_secs(360_000));
+        assert_eq!(parse("P1DT2H"), time::Duration::from_secs(93_600));
+        assert_eq!(parse("P1Y"), time::Duration::from_secs(31_557_600));
+
+        let parse = |input| ::time::Duration::try_from(postgres::parse(input).unwrap()).unwrap();
+        assert_eq!(parse("1 day -01:00:00"), ::time::Duration::hours(23));
+        assert_eq!(parse("-00:00:30"), ::time::Duration::seconds(-30));
+
+        for input in ["", "1 week", "04:05", "3 days 04:60:00", "00:00:00.1234567"] {
+            assert!(postgres::parse(input).is_err(), "{input}");
+        }
+
+        let format = |input| postgres::format(&postgres::parse(input).unwrap()).unwrap();
+        assert_eq!(format("3 days 04:05:06.789"), "3 days 04:05:06.789");
+        assert_eq!(format("PT0S"), "00:00:00");
+        assert_eq!(format("-00:00:30"), "-00:00:30");
+        assert_eq!(format("-1 days -02:00:00"), "-1 days -02:00:00");
+        assert_eq!(format("1 day 02:00:00"), "1 day 02:00:00");
+        assert_eq!(format("PT24H0.0000015S"), "1 day 00:00:00.000002");
+    }
+
+    #[test]
+    fn test_dotnet() {
+        use std::time;
+
+        let parse = |input| ::time::Duration::try_from(dotnet::parse(input).unwrap()).unwrap();
+
+        assert_eq!(
+            parse("1.02:03:04.5000000"),
+            ::time::Duration::milliseconds(93_784_500)
+        );
+        assert_eq!(parse("-00:00:30"), ::time::Duration::seconds(-30));
+        assert_eq!(parse("-00:00:00.5"), ::time::Duration::milliseconds(-500));
+        assert_eq!(parse("3"), ::time::Duration::days(3));
+        assert_eq!(parse("1:30"), ::time::Duration::minutes(90));
+        assert_eq!(parse("1.02:03"), ::time::Duration::minutes(1_563));
+        assert_eq!(
+            parse("10675199.02:48:05.4775807"),
+            ::time::Duration::new(922_337_203_685, 477_580_700)
+        );
+
+        for input in [
+            "",
+            "24:00:00",
+            "00:60:00",
+            "1.02",
+            "00:00:00.12345678",
+            "+00:00:01",
+            "10675199.02:48:05.4775808",
+        ] {
+            assert!(dotnet::parse(input).is_err(), "{input}");
+        }
+
+        let format = |duration| dotnet::format(&duration::Container::from(duration));
+        assert_eq!(format(time::Duration::ZERO).unwrap(), "00:00:00");
+        assert_eq!(
+            format(time::Duration::from_millis(93_784_500)).unwrap(),
+            "1.02:03:04.5000000"
+        );
+        assert_eq!(
+            format(time::Duration::from_nanos(100)).unwrap(),
+            "00:00:00.0000001"
+        );
+        assert!(matches!(
+            format(time::Duration::from_nanos(150)),
+            Err(error::Error::Unrepresentable(_))
+        ));
+
+        assert_eq!(dotnet::to_systemd("00:01:30").unwrap(), "1min 30s");
+        assert_eq!(dotnet::to_systemd("45.00:00:00").unwrap(), "6w 3d");
+        assert_eq!(dotnet::from_systemd("-30s").unwrap(), "-00:00:30");
+        assert_eq!(
+            dotnet::from_systemd(&dotnet::to_systemd("1.02:03:04.0000001").unwrap()).unwrap(),
+            "1.02:03:04.0000001"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "with-prost")]
+    fn test_protobuf() {
+        use protobuf::ProtobufExt;
+        use std::time;
+
+        let parse = |input| ::time::Duration::try_from(protobuf::parse(input).unwrap()).unwrap();
+
+        assert_eq!(parse("1.500s"), ::time::Duration::milliseconds(1_500));
+        assert_eq!(parse("-0.000000001s"), ::time::Duration::nanoseconds(-1));
+        assert_eq!(
+            parse("315576000000s"),
+            ::time::Duration::seconds(315_576_000_000)
+        );
+        for input in [
+            "",
+            "1",
+            "1.s",
+            "+1s",
+            "1.0000000001s",
+            "1 s",
+            "315576000001s",
+        ] {
+            assert!(protobuf::parse(input).is_err(), "{input}");
+        }
+
+        let pb = |seconds, nanos| prost_types::Duration { seconds, nanos };
+        assert_eq!(
+            time::Duration::from_millis(1_500).to_protobuf().unwrap(),
+            pb(1, 500_000_000)
+        );
+        assert_eq!(
+            ::time::Duration::milliseconds(-1_500)
+                .to_protobuf()
+                .unwrap(),
+            pb(-1, -500_000_000)
+        );
+        assert_eq!(
+            ::chrono::TimeDelta::milliseconds(-1_500)
+                .to_protobuf()
+                .unwrap(),
+            pb(-1, -500_000_000)
+        );
+        assert_eq!(
+            prost_types::Duration::try_from(parser::parse_container("2min").unwrap()).unwrap(),
+            pb(120, 0)
+        );
+        assert!(matches!(
+            time::Duration::from_secs(315_576_000_001).to_protobuf(),
+            Err(error::Error::DurationOverflow)
+        ));
+        assert!(matches!(
+            ::time::Duration::seconds(-315_576_000_001).to_protobuf(),
+            Err(error::Error::DurationOverflow)
+        ));
+        assert_eq!(
+            ::time::Duration::try_from(duration::Container::from(pb(-1, -500_000_000))).unwrap(),
+            ::time::Duration::milliseconds(-1_500)
+        );
+
+        let format =
+            |seconds, nanos| protobuf::format(&duration::Container::from(pb(seconds, nanos)));
+        assert_eq!(format(0, 0).unwrap(), "0s");
+        assert_eq!(format(1, 500_000_000).unwrap(), "1.500s");
+        assert_eq!(format(1, 10_000).unwrap(), "1.000010s");
+        assert_eq!(format(0, 1).unwrap(), "0.000000001s");
+        assert_eq!(format(0, -500_000_000).unwrap(), "-0.500s");
+
+        assert_eq!(protobuf::to_systemd("90s").unwrap(), "1min 30s");
+        assert_eq!(protobuf::from_systemd("-1.5min").unwrap(), "-90s");
+    }
+
+    #[test]
+    fn test_ffmpeg() {
+        use std::time;
+
+        let parse = |input| ::time::Duration::try_from(ffmpeg::parse(input).unwrap()).unwrap();
+
+        assert_eq!(
+            parse("01:30:00.5"),
+            ::time::Duration::milliseconds(5_400_500)
+        );
+        assert_eq!(parse("100:00:00"), ::time::Duration::hours(100));
+        assert_eq!(parse("2:05"), ::time::Duration::seconds(125));
+        assert_eq!(parse("-00:00:30"), ::time::Duration::seconds(-30));
+        assert_eq!(parse("90.5"), ::time::Duration::milliseconds(90_500));
+        assert_eq!(parse("90s"), ::time::Duration::seconds(90));
+        assert_eq!(parse("1.5ms"), ::time::Duration::microseconds(1_500));
+        assert_eq!(parse("1.5us"), ::time::Duration::microseconds(1));
+        assert_eq!(parse("-250ms"), ::time::Duration::milliseconds(-250));
+        assert_eq!(parse("0.1234567"), ::time::Duration::microseconds(123_456));
+        assert_eq!(parse("1."), ::time::Duration::seconds(1));
+        assert_eq!(parse("+1"), ::time::Duration::seconds(1));
+        assert_eq!(parse("-+1"), ::time::Duration::seconds(-1));
+        assert_eq!(parse("1:00ms"), ::time::Duration::milliseconds(60));
+        assert_eq!(parse("1:00:00.5us"), ::time::Duration::microseconds(3_600));
+        assert_eq!(parse("1:00.5s"), ::time::Duration::milliseconds(60_500));
+        assert!(ffmpeg::parse("9223372036854775807us").is_ok());
+
+        for input in [
+            "",
+            "s",
+            ".5",
+            "--1",
+            "+1:00",
+            "60:00",
+            "1:60:00",
+            "1m",
+            "9223372036854775808us",
+            "9223372036855s",
+        ] {
+            assert!(ffmpeg::parse(input).is_err(), "{input}");
+        }
+
+        let format = |duration| ffmpeg::format(&duration::Container::from(duration));
+        assert_eq!(format(time::Duration::ZERO).unwrap(), "00:00:00");
+        assert_eq!(
+            format(time::Duration::from_millis(5_400_500)).unwrap(),
+            "01:30:00.5"
+        );
+        assert_eq!(
+            format(time::Duration::from_secs(360_000)).unwrap(),
+            "100:00:00"
+        );
+        assert_eq!(
+            format(time::Duration::from_micros(1)).unwrap(),
+            "00:00:00.000001"
+        );
+        assert!(matches!(
+            format(time::Duration::from_nanos(1_500)),
+            Err(error::Error::Unrepresentable(_))
+        ));
+
+        assert_eq!(ffmpeg::to_systemd("-1.5").unwrap(), "-1.5s");
+        assert_eq!(ffmpeg::from_systemd("-90s").unwrap(), "-00:01:30");
+        assert_eq!(
+            ffmpeg::from_systemd(&ffmpeg::to_systemd("12:34:56.789012").unwrap()).unwrap(),
+            "12:34:56.789012"
+        );
+    }
+
+    #[test]
+    fn test_coreutils() {
+        use coreutils::Interval;
+        use std::time;
+
+        let parse = |input| match coreutils::parse(input).unwrap() {
+            Interval::Finite(container) => time::Duration::try_from(container).unwrap(),
+            Interval::Infinite => panic!("{input} is infinite"),
+        };
+
+        assert_eq!(parse("1.5m"), time::Duration::from_secs(90));
+        assert_eq!(parse("2d"), time::Duration::from_secs(172_800));
+        assert_eq!(parse(".5"), time::Duration::from_millis(500));
+        assert_eq!(parse("5."), time::Duration::from_secs(5));
+        assert_eq!(parse("1e3s"), time::Duration::from_secs(1_000));
+        assert_eq!(parse("+1h"), time::Duration::from_secs(3_600));
+        assert_eq!(parse("1m 30 0.5s"), time::Duration::from_millis(90_500));
+        for input in ["inf", "infinity", "INFINITY", "infd", "1s infinity"] {
+            assert!(
+                matches!(coreutils::parse(input).unwrap(), Interval::Infinite),
+                "{input}"
+            );
+        }
+
+        for input in ["", "-1", "0x10", "1w", "1ms", "1 m", "nan", "1e", "1e400"] {
+            assert!(coreutils::parse(input).is_err(), "{input}");
+        }
+
+        let format = |duration| coreutils::format(&duration::Container::from(duration));
+        assert_eq!(format(time::Duration::ZERO).unwrap(), "0");
+        assert_eq!(format(time::Duration::from_secs(172_800)).unwrap(), "2d");
+        assert_eq!(format(time::Duration::from_secs(5_400)).unwrap(), "90m");
+        assert_eq!(format(time::Duration::from_secs(90)).unwrap(), "90s");
+        assert_eq!(
+            format(time::Duration::from_nanos(1_500)).unwrap(),
+            "0.0000015s"
+        );
+        assert!(matches!(
+            coreutils::format(&duration::Container::from(::time::Duration::seconds(-1))),
+            Err(error::Error::Unrepresentable(_))
+        ));
+
+        assert_eq!(coreutils::to_systemd("1.5m 2d").unwrap(), "2d 1min 30s");
+        assert_eq!(coreutils::to_systemd("inf").unwrap(), "infinity");
+        assert_eq!(coreutils::to_systemd("400d").unwrap(), "57w 1d");
+        assert_eq!(coreutils::from_systemd("1h 30min").unwrap(), "90m");
+        assert_eq!(coreutils::from_systemd("infinity").unwrap(), "infinity");
+    }
+
+    #[test]
+    fn test_eventbridge() {
+        use std::time;
+
+        let parse = |input| time::Duration::try_from(eventbridge::parse(input).unwrap()).unwrap();
+
+        assert_eq!(parse("rate(5 minutes)"), time::Duration::from_secs(300));
+        assert_eq!(parse("rate(1 hour)"), time::Duration::from_secs(3_600));
+        assert_eq!(parse("rate(1 day)"), time::Duration::from_secs(86_400));
+        assert_eq!(
+            parse(" rate( 2 days ) "),
+            time::Duration::from_secs(172_800)
+        );
+
+        for input in [
+            "",
+            "rate()",
+            "rate(5)",
+            "rate(1 minutes)",
+            "rate(5 minute)",
+            "rate(0 minutes)",
+            "rate(-5 minutes)",
+            "rate(1.5 hours)",
+            "rate(5 seconds)",
+            "rate(5minutes)",
+            "RATE(5 minutes)",
+        ] {
+            assert!(eventbridge::parse(input).is_err(), "{input}");
+        }
+
+        let format = |duration| eventbridge::format(&duration::Container::from(duration));
+        assert_eq!(
+            format(time::Duration::from_secs(60)).unwrap(),
+            "rate(1 minute)"
+        );
+        assert_eq!(
+            format(time::Duration::from_secs(5_400)).unwrap(),
+            "rate(90 minutes)"
+        );
+        assert_eq!(
+            format(time::Duration::from_secs(7_200)).unwrap(),
+            "rate(2 hours)"
+        );
+        assert_eq!(
+            format(time::Duration::from_secs(86_400)).unwrap(),
+            "rate(1 day)"
+        );
+        for duration in [time::Duration::ZERO, time::Duration::from_secs(90)] {
+            assert!(matches!(
+                format(duration),
+                Err(error::Error::Unrepresentable(_))
+            ));
+        }
+
+        assert_eq!(
+            eventbridge::to_systemd("rate(90 minutes)").unwrap(),
+            "1h 30min"
+        );
+        // systemd reads months as a different length, so they are left out
+        assert_eq!(eventbridge::to_systemd("rate(60 days)").unwrap(), "8w 4d");
+        assert_eq!(eventbridge::from_systemd("1 week").unwrap(), "rate(7 days)");
+    }
+
+    #[test]
+    fn test_dialect() {
+        use dialect::Dialect;
+
+        // A dialect from outside the crate
+        struct Minutes;
+        impl Dialect for Minutes {
+            fn name(&self) -> &'static str {
+                "minutes"
+            }
+            fn parse(&self, input: &str) -> Result<duration::Container, error::Error> {
+                let minutes = input
+                    .parse::<u32>()
+                    .map_err(|_| error::Error::InvalidPhrase { offset: 0 })?;
+                Ok(duration::Container::new(vec![duration::Duration::Minute(
+                    f64::from(minutes),
+                )]))
+            }
+            fn format(&self, container: &duration::Container) -> Result<String, error::Error> {
+                Ok((container.nanos()? / 60_000_000_000).to_string())
+            }
+        }
+
+        // The names a report should list, with those of optional dialects which are enabled
+        let humantime = cfg!(feature = "with-humantime").then_some("humantime");
+        let protobuf = cfg!(feature = "with-prost").then_some("protobuf");
+        let expected = |names: &[&'static str], optional: &[Option<&'static str>]| {
+            names
+                .iter()
+                .copied()
+                .chain(optional.iter().flatten().copied())
+                .collect::<Vec<_>>()
+        };
+
+        let report = dialect::detect("90");
+        assert_eq!(report.names(), ["systemd", "dotnet", "ffmpeg", "coreutils"]);
+        assert!(report.is_ambiguous());
+
+        let report = dialect::detect("1y");
+        assert_eq!(
+            report.names(),
+            expected(&["systemd", "prometheus"], &[humantime])
+        );
+        assert!(report.is_ambiguous());
+
+        // Read ignoring case, systemd's months would be minutes
+        let report = dialect::detect("1M");
+        assert_eq!(report.names(), expected(&["systemd"], &[humantime]));
+        assert_eq!(report.detections[0].case_collision.as_deref(), Some("M"));
+        assert!(report.is_ambiguous());
+
+        let report = dialect::detect("1m");
+        assert_eq!(
+            report.names(),
+            expected(&["systemd", "go", "prometheus", "coreutils"], &[humantime])
+        );
+        assert!(report.detections[0].case_collision.is_none());
+        assert!(!report.is_ambiguous());
+
+        let report = dialect::detect("90s");
+        assert_eq!(
+            report.names(),
+            expected(
+                &["systemd", "go", "prometheus", "ffmpeg", "coreutils"],
+                &[humantime, protobuf]
+            )
+        );
+        assert!(report
+            .detections
+            .iter()
+            .all(|detection| detection.case_collision.is_none()));
+        assert!(!report.is_ambiguous());
+
+        assert!(dialect::detect("not a duration").detections.is_empty());
+
+        // Every dialect formats what it parses back to the same length
+        let container = parser::parse_container("1h 30min").unwrap();
+        for dialect in dialect::DIALECTS {
+            let formatted = dialect.format(&container).unwrap();
+            let parsed = dialect.parse(&formatted).unwrap();
+            assert_eq!(
+                parsed.nanos().unwrap(),
+                container.nanos().unwrap(),
+                "{}",
+                dialect.name()
+            );
+        }
+
+        // Other dialects can be detected alongside these
+        let report = dialect::detect_with("90", [&dialect::Systemd as &dyn Dialect, &Minutes]);
+        assert_eq!(report.names(), ["systemd", "minutes"]);
+        assert!(report.is_ambiguous());
+    }
+
+    #[test]
+    #[cfg(feature = "with-humantime")]
+    fn test_humantime() {
+        use std::time;
+
+        let duration = ::humantime::Duration::from(time::Duration::from_millis(5_400_500));
+        let container = duration::Container::from(duration);
+        assert_eq!(
+            ::humantime::Duration::try_from(container).unwrap(),
+            duration
+        );
+        assert!(::humantime::Duration::try_from(parser::parse_container("-1s").unwrap()).is_err());
+
+        for input in ["1h 30min", "2 days", "1.5h", "90s", "1msec", "3 weeks"] {
+            assert!(humantime::compare(input).is_identical(), "{input}");
+        }
+
+        let compatibility = humantime::compare("1y");
+        assert_eq!(compatibility.difference(), Some(-648_000_000_000));
+        let compatibility = humantime::compare("5millis");
+        assert!(compatibility.systemd.is_err());
+        assert_eq!(
+            compatibility.humantime.unwrap(),
+            time::Duration::from_millis(5)
+        );
+        let compatibility = humantime::compare("1\u{3bc}s");
+        assert!(compatibility.systemd.is_ok());
+        assert!(compatibility.humantime.is_err());
+        assert!(!compatibility.is_identical());
+
+        assert!(matches!(
+            humantime::parse("1 fortnight"),
+            Err(error::Error::InvalidDuration(_))
+        ));
+        assert_eq!(humantime::to_systemd("2hrs 5millis").unwrap(), "2h 5ms");
+        assert_eq!(humantime::from_systemd("1.5h").unwrap(), "1h 30m");
+        assert!(dialect::detect("1wk").names().contains(&"humantime"));
+    }
+
     #[test]
     fn test_stdtime_duration_negative_invalid() {
         assert!(parser::stdtime::parse("-30d").is_err());
@@ -1467,6 +2725,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_chrono_duration_negative_fractional() {
+        assert_eq!(
+            parser::chrono::parse("-1.5s").unwrap(),
+            ::chrono::TimeDelta::milliseconds(-1_500)
+        );
+        assert_eq!(
+            parser::chrono::parse("-0.000000001s").unwrap(),
+            ::chrono::TimeDelta::nanoseconds(-1)
+        );
+        assert_eq!(
+            parser::chrono::parse("-2.25h").unwrap(),
+            ::chrono::TimeDelta::seconds(-8_100)
+        );
+    }
+
     #[test]
     fn test_duration_fractional() {
         use std::time;
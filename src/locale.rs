@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Unit names in other languages, for parsing and formatting.
+//!
+//! The default parser only accepts systemd's English unit names. A locale can be given in
+//! [`parser::Options`][crate::parser::Options] to accept its names too, and in
+//! [`format::Options`][crate::format::Options] to use them in the verbose style.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::{locale, parser::Options, stdtime};
+//!
+//! let options = Options {
+//!     locale: Some(&locale::GERMAN),
+//!     ..Options::default()
+//! };
+//! let duration = stdtime::parse_with("2 Stunden 30 Minuten", &options)
+//!     .expect("Could not parse duration");
+//! assert_eq!(duration, Duration::from_secs(9_000));
+//! ```
+
+use crate::duration::Unit;
+
+/// Unit names in a language.
+#[derive(Debug)]
+pub struct Locale {
+    /// The language tag, such as `de`
+    pub tag: &'static str,
+    /// The singular and plural names of each unit
+    pub names: &'static [(Unit, &'static str, &'static str)],
+    /// Other names accepted when parsing, such as abbreviations or other grammatical cases
+    pub aliases: &'static [(&'static str, Unit)],
+    /// The word joining the last two items of a list, such as `and`
+    pub and: &'static str,
+    /// Whether a count takes the plural form of a unit
+    pub plural: fn(f64) -> bool,
+}
+
+impl Locale {
+    /// The name of a unit for the given count, such as `hour` for 1 or `hours` for 2.
+    #[must_use]
+    pub fn name(&self, unit: Unit, count: f64) -> &'static str {
+        let plural = (self.plural)(count);
+        self.names
+            .iter()
+            .find(|&&(u, _, _)| u == unit)
+            .map_or(
+                "",
+                |&(_, singular, plural_name)| {
+                    if plural {
+                        plural_name
+                    } else {
+                        singular
+                    }
+                },
+            )
+    }
+
+    /// Look up a whole word as a unit.
+    #[must_use]
+    pub fn unit(&self, word: &str) -> Option<Unit> {
+        self.lookup(word).map(|(_, unit)| unit)
+    }
+
+    // Look up a whole word as a unit, also returning the name as it appears in the tables
+    fn lookup(&self, word: &str) -> Option<(&'static str, Unit)> {
+        self.entries().find(|&(name, _)| name == word)
+    }
+
+    // Every name accepted when parsing, along with its unit
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (&'static str, Unit)> {
+        self.names
+            .iter()
+            .flat_map(|&(unit, singular, plural)| [(singular, unit), (plural, unit)])
+            .chain(self.aliases.iter().copied())
+    }
+}
+
+fn plural_unless_one(count: f64) -> bool {
+    (count - 1.0).abs() >= f64::EPSILON
+}
+
+// French uses the singular for anything less than two
+fn plural_from_two(count: f64) -> bool {
+    count.abs() >= 2.0
+}
+
+/// English unit names.
+pub static ENGLISH: Locale = Locale {
+    tag: "en",
+    names: &[
+        (Unit::Year, "year", "years"),
+        (Unit::Month, "month", "months"),
+        (Unit::Week, "week", "weeks"),
+        (Unit::Day, "day", "days"),
+        (Unit::Hour, "hour", "hours"),
+        (Unit::Minute, "minute", "minutes"),
+        (Unit::Second, "second", "seconds"),
+        (Unit::Millisecond, "millisecond", "milliseconds"),
+        (Unit::Microsecond, "microsecond", "microseconds"),
+        (Unit::Nanosecond, "nanosecond", "nanoseconds"),
+    ],
+    aliases: &[],
+    and: "and",
+    plural: plural_unless_one,
+};
+
+/// German unit names.
+pub static GERMAN: Locale = Locale {
+    tag: "de",
+    names: &[
+        (Unit::Year, "Jahr", "Jahre"),
+        (Unit::Month, "Monat", "Monate"),
+        (Unit::Week, "Woche", "Wochen"),
+        (Unit::Day, "Tag", "Tage"),
+        (Unit::Hour, "Stunde", "Stunden"),
+        (Unit::Minute, "Minute", "Minuten"),
+        (Unit::Second, "Sekunde", "Sekunden"),
+        (Unit::Millisecond, "Millisekunde", "Millisekunden"),
+        (Unit::Microsecond, "Mikrosekunde", "Mikrosekunden"),
+        (Unit::Nanosecond, "Nanosekunde", "Nanosekunden"),
+    ],
+    aliases: &[
+        ("Jahren", Unit::Year),
+        ("Monaten", Unit::Month),
+        ("Tagen", Unit::Day),
+        ("Std", Unit::Hour),
+        ("Min", Unit::Minute),
+        ("Sek", Unit::Second),
+    ],
+    and: "und",
+    plural: plural_unless_one,
+};
+
+/// French unit names.
+pub static FRENCH: Locale = Locale {
+    tag: "fr",
+    names: &[
+        (Unit::Year, "an", "ans"),
+        (Unit::Month, "mois", "mois"),
+        (Unit::Week, "semaine", "semaines"),
+        (Unit::Day, "jour", "jours"),
+        (Unit::Hour, "heure", "heures"),
+        (Unit::Minute, "minute", "minutes"),
+        (Unit::Second, "seconde", "secondes"),
+        (Unit::Millisecond, "milliseconde", "millisecondes"),
+        (Unit::Microsecond, "microseconde", "microsecondes"),
+        (Unit::Nanosecond, "nanoseconde", "nanosecondes"),
+    ],
+    aliases: &[("année", Unit::Year), ("années", Unit::Year)],
+    and: "et",
+    plural: plural_from_two,
+};
+
+/// Spanish unit names.
+pub static SPANISH: Locale = Locale {
+    tag: "es",
+    names: &[
+        (Unit::Year, "año", "años"),
+        (Unit::Month, "mes", "meses"),
+        (Unit::Week, "semana", "semanas"),
+        (Unit::Day, "día", "días"),
+        (Unit::Hour, "hora", "horas"),
+        (Unit::Minute, "minuto", "minutos"),
+        (Unit::Second, "segundo", "segundos"),
+        (Unit::Millisecond, "milisegundo", "milisegundos"),
+        (Unit::Microsecond, "microsegundo", "microsegundos"),
+        (Unit::Nanosecond, "nanosegundo", "nanosegundos"),
+    ],
+    aliases: &[("dia", Unit::Day), ("dias", Unit::Day)],
+    and: "y",
+    plural: plural_unless_one,
+};
+
+/// Portuguese unit names.
+pub static PORTUGUESE: Locale = Locale {
+    tag: "pt",
+    names: &[
+        (Unit::Year, "ano", "anos"),
+        (Unit::Month, "mês", "meses"),
+        (Unit::Week, "semana", "semanas"),
+        (Unit::Day, "dia", "dias"),
+        (Unit::Hour, "hora", "horas"),
+        (Unit::Minute, "minuto", "minutos"),
+        (Unit::Second, "segundo", "segundos"),
+        (Unit::Millisecond, "milissegundo", "milissegundos"),
+        (Unit::Microsecond, "microssegundo", "microssegundos"),
+        (Unit::Nanosecond, "nanossegundo", "nanossegundos"),
+    ],
+    aliases: &[("mes", Unit::Month)],
+    and: "e",
+    plural: plural_unless_one,
+};
+
+/// Every locale shipped with this crate.
+pub static LOCALES: [&Locale; 5] = [&ENGLISH, &GERMAN, &FRENCH, &SPANISH, &PORTUGUESE];
+
+/// Find a shipped locale by its language tag, such as `de` or `pt-BR`.
+///
+/// Only the language is considered, so `pt-BR` and `pt-PT` both give [`PORTUGUESE`].
+#[must_use]
+pub fn from_tag(tag: &str) -> Option<&'static Locale> {
+    let language = tag.split(['-', '_']).next().unwrap_or(tag);
+    LOCALES
+        .iter()
+        .copied()
+        .find(|locale| locale.tag.eq_ignore_ascii_case(language))
+}
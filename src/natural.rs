@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Parsing durations written in plain English, such as `an hour and a half`.
+//!
+//! This is separate from the systemd parser, and must be used explicitly. It understands:
+//!
+//! * numbers, either as digits (`2`, `1.5`) or words from `zero` to `ninety-nine`
+//! * `a` or `an` for one, and `a couple of` for two
+//! * halves, as in `half a day`, `a half hour` or `an hour and a half`
+//! * any unit name systemd accepts, such as `hours` or `min`, also written straight after a
+//!   number, as in `30min`
+//! * lists joined by commas and `and`, as in `2 hours, 5 minutes and 10 seconds`
+//!
+//! Anything else is rejected, including vague quantities such as `a few minutes`, rather than
+//! being guessed at.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::natural;
+//!
+//! let container = natural::parse("an hour and a half").expect("Could not parse duration");
+//! assert_eq!(Duration::try_from(container).unwrap(), Duration::from_secs(5_400));
+//! ```
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, parser,
+};
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+// A word of the input, along with its lowercase form and where it starts
+struct Word<'a> {
+    text: &'a str,
+    lower: String,
+    offset: usize,
+}
+
+// Split the input into words, with each comma as a word of its own
+fn words(input: &str) -> Vec<Word<'_>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (offset, c) in input.char_indices().chain([(input.len(), ' ')]) {
+        if c.is_whitespace() || c == ',' {
+            if let Some(start) = start.take() {
+                words.push(&input[start..offset]);
+            }
+            if c == ',' {
+                words.push(&input[offset..=offset]);
+            }
+        } else if start.is_none() {
+            start = Some(offset);
+        }
+    }
+
+    // Split numbers from units written straight after them, as in `30min`
+    let words = words.into_iter().flat_map(|text: &str| {
+        let number = text
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .filter(|&end| end > 0 && text.starts_with(|c: char| c.is_ascii_digit()))
+            .unwrap_or(text.len());
+        [&text[..number], &text[number..]]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+    });
+
+    words
+        .map(|text| Word {
+            text,
+            lower: text.to_lowercase(),
+            offset: text.as_ptr() as usize - input.as_ptr() as usize,
+        })
+        .collect()
+}
+
+// A number word below a hundred, such as `seven` or `forty-two`
+fn number_word(word: &str) -> Option<u8> {
+    let position = |table: &[&str], word| table.iter().position(|&w| w == word);
+    #[allow(clippy::cast_possible_truncation)]
+    if let Some(n) = position(&ONES, word) {
+        return Some(n as u8);
+    }
+
+    let (tens, ones) = match word.split_once('-') {
+        Some((tens, ones)) => (tens, position(&ONES[1..10], ones)? + 1),
+        None => (word, 0),
+    };
+    let tens = position(&TENS, tens)?;
+    #[allow(clippy::cast_possible_truncation)]
+    Some((tens as u8 + 2) * 10 + ones as u8)
+}
+
+// A number written in digits, such as `2` or `1.5`
+fn digits(word: &str) -> Option<f64> {
+    if !word.starts_with(|c: char| c.is_ascii_digit())
+        || !word.chars().all(|c| c.is_ascii_digit() || c == '.')
+    {
+        return None;
+    }
+    word.parse().ok()
+}
+
+// A unit name, matched exactly first so `M` stays months, then in lowercase
+fn unit(word: &Word<'_>) -> Option<Unit> {
+    parser::unit(word.text).or_else(|| parser::unit(&word.lower))
+}
+
+// The words of a phrase, and how far through them parsing is
+struct Phrase<'a> {
+    words: Vec<Word<'a>>,
+    pos: usize,
+    len: usize,
+}
+
+impl Phrase<'_> {
+    // The lowercase word `n` words ahead, if there is one
+    fn peek(&self, n: usize) -> Option<&str> {
+        self.words.get(self.pos + n).map(|w| w.lower.as_str())
+    }
+
+    // Skip the next word if it is one of the given words
+    fn eat(&mut self, words: &[&str]) -> bool {
+        let found = self.peek(0).is_some_and(|w| words.contains(&w));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    // Skip `and a half`, if it comes next
+    fn and_a_half(&mut self) -> bool {
+        let found = self.peek(0) == Some("and")
+            && matches!(self.peek(1), Some("a" | "an" | "one"))
+            && self.peek(2) == Some("half");
+        if found {
+            self.pos += 3;
+        }
+        found
+    }
+
+    // An error pointing at the current word, or the end of the input
+    fn fail(&self) -> error::Error {
+        error::Error::InvalidPhrase {
+            offset: self.words.get(self.pos).map_or(self.len, |w| w.offset),
+        }
+    }
+
+    // How many of a unit there are, such as `a`, `half a` or `twenty-five`
+    fn quantity(&mut self) -> Result<f64, error::Error> {
+        if self.eat(&["half"]) {
+            self.eat(&["a", "an"]);
+            return Ok(0.5);
+        }
+        if self.eat(&["a", "an"]) {
+            if self.eat(&["half"]) {
+                return Ok(0.5);
+            }
+            if self.eat(&["couple"]) {
+                if !self.eat(&["of"]) {
+                    return Err(self.fail());
+                }
+                return Ok(2.0);
+            }
+            return Ok(1.0);
+        }
+
+        let word = self.peek(0).ok_or_else(|| self.fail())?;
+        let count = digits(word)
+            .or_else(|| number_word(word).map(f64::from))
+            .ok_or_else(|| self.fail())?;
+        self.pos += 1;
+        if self.and_a_half() {
+            return Ok(count + 0.5);
+        }
+        Ok(count)
+    }
+
+    // A quantity followed by a unit, such as `an hour and a half`
+    fn item(&mut self) -> Result<Duration, error::Error> {
+        let mut count = self.quantity()?;
+        let name = self
+            .words
+            .get(self.pos)
+            .and_then(unit)
+            .ok_or_else(|| self.fail())?;
+        self.pos += 1;
+
+        if count.fract() == 0.0 && self.and_a_half() {
+            // `an hour and a half minutes` could mean either unit
+            if self.words.get(self.pos).and_then(unit).is_some() {
+                return Err(self.fail());
+            }
+            count += 0.5;
+        }
+
+        Duration::from_count(name, count).ok_or(error::Error::DurationOverflow)
+    }
+}
+
+/// Parse a duration written in plain English, such as `half a day` or `2 hours and 15 minutes`.
+///
+/// Keywords and unit names are matched regardless of case, except where systemd's names differ
+/// only in case, such as `M` for months and `m` for minutes.
+///
+/// # Errors
+///
+/// Returns [`error::Error::InvalidPhrase`] with the offset of the first word that could not be
+/// understood, or the length of the input if it ended too soon.
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    let mut phrase = Phrase {
+        words: words(input),
+        pos: 0,
+        len: input.len(),
+    };
+
+    let mut durations = vec![phrase.item()?];
+    while phrase.pos < phrase.words.len() {
+        // Items may be separated by commas, `and`, both, or nothing
+        let comma = phrase.eat(&[","]);
+        let and = phrase.eat(&["and"]);
+        if (comma || and) && phrase.pos == phrase.words.len() {
+            return Err(phrase.fail());
+        }
+        durations.push(phrase.item()?);
+    }
+
+    Ok(Container::new(durations))
+}
@@ -9,12 +9,17 @@
 // You should have received a copy of the CC0 Public Domain Dedication along
 // with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
 
+use std::ffi::OsStr;
+
 use nom::{
     branch::alt,
-    bytes::complete::tag,
+    bytes::complete::take_while1,
     character::complete::{char, digit0, digit1, multispace0, one_of},
     combinator::{all_consuming, complete, cut, map, opt, recognize},
-    error::{ErrorKind::TooLarge, ParseError},
+    error::{
+        ErrorKind::{Tag, TooLarge, Verify},
+        ParseError,
+    },
     multi::many1,
     sequence::delimited,
     Err::Failure,
@@ -22,27 +27,47 @@ use nom::{
 };
 
 use crate::{
-    duration::{Container, Duration},
+    duration::{Container, Duration, Unit},
     error,
+    locale::Locale,
 };
 
-// Dimensionless unit constants
-#[derive(Copy, Clone, Debug)]
-enum DurationUnit {
-    Year,
-    Month,
-    Week,
-    Day,
-    Hour,
-    Minute,
-    Second,
-    Millisecond,
-    Microsecond,
-    Nanosecond,
+/// How the case of unit names is matched.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Case {
+    /// Unit names must be written exactly as listed, like systemd
+    #[default]
+    Sensitive,
+    /// Unit names may be written in any case, such as `1H` or `30MIN`
+    Insensitive(Collision),
+}
+
+/// How names which only differ in case are told apart when matching case-insensitively.
+///
+/// Systemd relies on one such pair: `M` for months and `m` for minutes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Collision {
+    /// A name written in exactly the listed case is that unit, so `M` is months and `m` is
+    /// minutes. Other spellings which could be more than one unit are ambiguous.
+    #[default]
+    ExactCase,
+    /// Any name which could be more than one unit is ambiguous, even in its exact case
+    Reject,
+}
+
+/// Options for parsing durations.
+///
+/// The defaults parse exactly what systemd accepts.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Also accept the unit names of this locale, alongside systemd's
+    pub locale: Option<&'static Locale>,
+    /// How the case of unit names is matched
+    pub case: Case,
 }
 
 // NOTE: we don't accept full float syntax. Systemd doesn't, so this isn't a problem.
-fn float(input: &str) -> IResult<&str, f64> {
+fn float<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, f64, E> {
     map(
         recognize((opt(one_of("+-")), opt((digit0, char('.'))), digit1)),
         |s: &str| s.parse::<f64>().unwrap(),
@@ -50,233 +75,278 @@ fn float(input: &str) -> IResult<&str, f64> {
     .parse(input)
 }
 
-fn timespan_word(input: &str) -> IResult<&str, &str> {
+fn timespan_word<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
     // XXX - not fantastic but don't have a better way right now
-    recognize(many1(one_of("Macdehiklmnorstuwyµ"))).parse(input)
+    recognize(many1(one_of("Macdehiklmnorstuwyµμ"))).parse(input)
 }
 
-// This is used to get the longest possible match for a string
-#[must_use]
-fn all_consuming_tag<'a, E>(
-    t: &'a str,
-) -> impl Parser<&'a str, Output = &'a str, Error = E> + 'a
-where
-    E: ParseError<&'a str> + 'a,
-{
-    all_consuming(tag(t))
-}
+// Every unit name systemd accepts. Names for the same unit are kept together.
+pub(crate) const UNITS: &[(&str, Unit)] = &[
+    ("years", Unit::Year),
+    ("year", Unit::Year),
+    ("yrs", Unit::Year),
+    ("yr", Unit::Year),
+    ("y", Unit::Year),
+    ("months", Unit::Month),
+    ("month", Unit::Month),
+    ("mos", Unit::Month),
+    ("mo", Unit::Month),
+    ("M", Unit::Month),
+    ("weeks", Unit::Week),
+    ("week", Unit::Week),
+    ("wks", Unit::Week),
+    ("wk", Unit::Week),
+    ("w", Unit::Week),
+    ("days", Unit::Day),
+    ("day", Unit::Day),
+    ("d", Unit::Day),
+    ("hours", Unit::Hour),
+    ("hour", Unit::Hour),
+    ("hrs", Unit::Hour),
+    ("hr", Unit::Hour),
+    ("h", Unit::Hour),
+    ("minutes", Unit::Minute),
+    ("minute", Unit::Minute),
+    ("mins", Unit::Minute),
+    ("min", Unit::Minute),
+    ("m", Unit::Minute),
+    ("seconds", Unit::Second),
+    ("second", Unit::Second),
+    ("secs", Unit::Second),
+    ("sec", Unit::Second),
+    ("s", Unit::Second),
+    ("milliseconds", Unit::Millisecond),
+    ("millisecond", Unit::Millisecond),
+    ("msecs", Unit::Millisecond),
+    ("msec", Unit::Millisecond),
+    ("ms", Unit::Millisecond),
+    ("microseconds", Unit::Microsecond),
+    ("microsecond", Unit::Microsecond),
+    ("µsecs", Unit::Microsecond),
+    ("µsec", Unit::Microsecond),
+    ("µs", Unit::Microsecond),
+    ("µ", Unit::Microsecond),
+    ("μsecs", Unit::Microsecond),
+    ("μsec", Unit::Microsecond),
+    ("μs", Unit::Microsecond),
+    ("μ", Unit::Microsecond),
+    ("usecs", Unit::Microsecond),
+    ("usec", Unit::Microsecond),
+    ("us", Unit::Microsecond),
+    ("nanoseconds", Unit::Nanosecond),
+    ("nanosecond", Unit::Nanosecond),
+    ("nsecs", Unit::Nanosecond),
+    ("nsec", Unit::Nanosecond),
+    ("ns", Unit::Nanosecond),
+];
 
-fn timespan_period_years(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("years"),
-            all_consuming_tag("year"),
-            all_consuming_tag("yrs"),
-            all_consuming_tag("yr"),
-            all_consuming_tag("y"),
-        )),
-        |_| DurationUnit::Year,
-    )
-    .parse(input)
+// Look up a whole word in the unit table
+fn lookup(word: &str) -> Option<(&'static str, Unit)> {
+    UNITS.iter().find(|&&(name, _)| name == word).copied()
 }
 
-fn timespan_period_months(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("months"),
-            all_consuming_tag("month"),
-            all_consuming_tag("mos"),
-            all_consuming_tag("mo"),
-            all_consuming_tag("M"),
-        )),
-        |_| DurationUnit::Month,
-    )
-    .parse(input)
+// Look up a whole word as a unit
+pub(crate) fn unit(word: &str) -> Option<Unit> {
+    lookup(word).map(|(_, unit)| unit)
 }
 
-fn timespan_period_weeks(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("weeks"),
-            all_consuming_tag("week"),
-            all_consuming_tag("wks"),
-            all_consuming_tag("wk"),
-            all_consuming_tag("w"),
-        )),
-        |_| DurationUnit::Week,
-    )
-    .parse(input)
+// Whether two names are the same apart from case
+fn fold_eq(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
 }
 
-fn timespan_period_days(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("days"),
-            all_consuming_tag("day"),
-            all_consuming_tag("d"),
-        )),
-        |_| DurationUnit::Day,
-    )
-    .parse(input)
+// Look up a whole word in the unit tables the options allow. Errors if the word is ambiguous.
+fn lookup_with(options: &Options, word: &str) -> Result<Option<(&'static str, Unit)>, ()> {
+    let entries = || {
+        UNITS
+            .iter()
+            .copied()
+            .chain(options.locale.into_iter().flat_map(Locale::entries))
+    };
+    let ambiguous = |unit| entries().any(|(name, u)| u != unit && fold_eq(name, word));
+
+    if let Some(entry) = entries().find(|&(name, _)| name == word) {
+        if options.case == Case::Insensitive(Collision::Reject) && ambiguous(entry.1) {
+            return Err(());
+        }
+        return Ok(Some(entry));
+    }
+    if options.case == Case::Sensitive {
+        return Ok(None);
+    }
+
+    match entries().find(|&(name, _)| fold_eq(name, word)) {
+        Some(entry) if ambiguous(entry.1) => Err(()),
+        entry => Ok(entry),
+    }
 }
 
-fn timespan_period_hours(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("hours"),
-            all_consuming_tag("hour"),
-            all_consuming_tag("hrs"),
-            all_consuming_tag("hr"),
-            all_consuming_tag("h"),
-        )),
-        |_| DurationUnit::Hour,
-    )
-    .parse(input)
+// Match a timespan period, consisting of an entire word
+// If the word isn't a unit, this fails. Returns the name of the unit as well.
+fn timespan_period<'a, E: ParseError<&'a str>>(
+    options: &Options,
+    input: &'a str,
+) -> IResult<&'a str, (&'static str, Unit), E> {
+    // Localized names and other cases may use any letter
+    let (rest, word) = if options.locale.is_some() || options.case != Case::Sensitive {
+        take_while1(char::is_alphabetic).parse(input)?
+    } else {
+        timespan_word(input)?
+    };
+    lookup_with(options, word)
+        .map_err(|()| Failure(E::from_error_kind(input, Verify)))?
+        .map(|entry| (rest, entry))
+        .ok_or_else(|| nom::Err::Error(E::from_error_kind(input, Tag)))
 }
 
-fn timespan_period_minutes(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("minutes"),
-            all_consuming_tag("minute"),
-            all_consuming_tag("mins"),
-            all_consuming_tag("min"),
-            all_consuming_tag("m"),
-        )),
-        |_| DurationUnit::Minute,
-    )
-    .parse(input)
+// Parse a fragment of a duration, along with the name of its unit
+pub(crate) fn aliased_fragment<'a, E: ParseError<&'a str>>(
+    options: &Options,
+    input: &'a str,
+) -> IResult<&'a str, (Duration, &'static str), E> {
+    let (input, count) = delimited(multispace0, float, multispace0).parse(input)?;
+    let (input, (alias, unit)) = timespan_period(options, input)?;
+    let val = Duration::from_count(unit, count)
+        .ok_or_else(|| Failure(E::from_error_kind(input, TooLarge)))?;
+
+    Ok((input, (val, alias)))
 }
 
-fn timespan_period_seconds(input: &str) -> IResult<&str, DurationUnit> {
+/// Parse a single fragment of a duration, such as `30min`, skipping any leading whitespace.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a number followed by a unit. Returns a
+/// [`Failure`] if a nanosecond count does not fit in an [`i64`].
+#[inline(never)]
+pub fn duration_fragment<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Duration, E> {
     map(
-        alt((
-            all_consuming_tag("seconds"),
-            all_consuming_tag("second"),
-            all_consuming_tag("secs"),
-            all_consuming_tag("sec"),
-            all_consuming_tag("s"),
-        )),
-        |_| DurationUnit::Second,
+        |i| aliased_fragment(&Options::default(), i),
+        |(duration, _)| duration,
     )
     .parse(input)
 }
 
-fn timespan_period_milliseconds(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("milliseconds"),
-            all_consuming_tag("millisecond"),
-            all_consuming_tag("msecs"),
-            all_consuming_tag("msec"),
-            all_consuming_tag("ms"),
-        )),
-        |_| DurationUnit::Millisecond,
-    )
-    .parse(input)
+// If nothing else is input, just interpret it as seconds.
+fn raw_seconds<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Duration, E> {
+    let (input, seconds) = delimited(multispace0, float, multispace0).parse(input)?;
+    Ok((input, Duration::Second(seconds)))
 }
 
-fn timespan_period_microseconds(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("microseconds"),
-            all_consuming_tag("microsecond"),
-            all_consuming_tag("µsecs"),
-            all_consuming_tag("µsec"),
-            all_consuming_tag("µs"),
-            all_consuming_tag("µ"),
-            all_consuming_tag("usecs"),
-            all_consuming_tag("usec"),
-            all_consuming_tag("us"),
-        )),
-        |_| DurationUnit::Microsecond,
-    )
-    .parse(input)
+/// Parse a duration from the start of the input, returning the remaining input.
+///
+/// This consumes as many fragments as possible, so `1h30m;` leaves `;` behind. A number with no
+/// unit at all is interpreted as seconds.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a duration. See also
+/// [`duration_fragment`].
+pub fn duration<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    duration_with(&Options::default(), input)
 }
 
-fn timespan_period_nanoseconds(input: &str) -> IResult<&str, DurationUnit> {
-    map(
-        alt((
-            all_consuming_tag("nanoseconds"),
-            all_consuming_tag("nanosecond"),
-            all_consuming_tag("nsecs"),
-            all_consuming_tag("nsec"),
-            all_consuming_tag("ns"),
-        )),
-        |_| DurationUnit::Nanosecond,
-    )
+/// Parse a duration from the start of the input with the given options, returning the remaining
+/// input.
+///
+/// # Errors
+///
+/// See [`duration`].
+pub fn duration_with<'a, E: ParseError<&'a str>>(
+    options: &Options,
+    input: &'a str,
+) -> IResult<&'a str, Container, E> {
+    alt((
+        map(many1(|i| aliased_fragment(options, i)), |fragments| {
+            let (durations, aliases) = fragments.into_iter().unzip();
+            Container::with_aliases(durations, aliases)
+        }),
+        map(raw_seconds, |v| Container::with_aliases(vec![v], vec![""])),
+    ))
     .parse(input)
 }
 
-// Match a timespan period, consisting of an entire word
-// If the string isn't consumed, this fails.
-fn timespan_period(input: &str) -> IResult<&str, DurationUnit> {
-    let (input, unit) = timespan_word(input)?;
-    let (_, result) = all_consuming(alt((
-        timespan_period_years,
-        timespan_period_months,
-        timespan_period_weeks,
-        timespan_period_days,
-        timespan_period_hours,
-        timespan_period_minutes,
-        timespan_period_seconds,
-        timespan_period_milliseconds,
-        timespan_period_microseconds,
-        timespan_period_nanoseconds,
-    )))
-    .parse(unit)?;
-
-    Ok((input, result))
+// Parse an entire string as a duration
+fn full_duration<'a>(options: &Options, input: &'a str) -> IResult<&'a str, Container> {
+    complete(cut(all_consuming(|i| duration_with(options, i)))).parse(input)
 }
 
-// Returns a fragment of the duration
-#[inline(never)]
-fn duration_fragment(input: &str) -> IResult<&str, Duration> {
-    let (input, count) = delimited(multispace0, float, multispace0).parse(input)?;
-    let (input, unit) = timespan_period(input)?;
-    let val = match unit {
-        DurationUnit::Year => Duration::Year(count),
-        DurationUnit::Month => Duration::Month(count),
-        DurationUnit::Week => Duration::Week(count),
-        DurationUnit::Day => Duration::Day(count),
-        DurationUnit::Hour => Duration::Hour(count),
-        DurationUnit::Minute => Duration::Minute(count),
-        DurationUnit::Second => Duration::Second(count),
-        DurationUnit::Millisecond => Duration::Millisecond(count),
-        DurationUnit::Microsecond => Duration::Microsecond(count),
-        DurationUnit::Nanosecond => {
-            // All numbers are specified as floats, and a 52-bit mantissa is more than enough for
-            // most nanosecond values, so this is fine.
-            #[allow(clippy::cast_precision_loss)]
-            if count < i64::MIN as f64 || count > i64::MAX as f64 {
-                return Err(Failure(ParseError::from_error_kind(input, TooLarge)));
-            }
-            #[allow(clippy::cast_possible_truncation)]
-            Duration::Nanosecond(count as i64)
-        }
-    };
+// Convert a parser error, picking out ambiguous unit names
+fn parser_error(e: nom::error::Error<String>) -> error::Error {
+    if e.code == Verify {
+        let end = e
+            .input
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(e.input.len());
+        error::Error::AmbiguousUnit(e.input[..end].to_owned())
+    } else {
+        e.into()
+    }
+}
 
-    Ok((input, val))
+// Parse an entire string with the parser of one of the other formats
+pub(crate) fn parse_all<'a, O>(
+    parser: impl Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+    input: &'a str,
+) -> Result<O, error::Error> {
+    let result: IResult<&str, O> = complete(cut(all_consuming(parser))).parse(input);
+    let (_, output) = result
+        .map_err(nom::Err::<nom::error::Error<&str>>::to_owned)
+        .finish()?;
+    Ok(output)
 }
 
-// If nothing else is input, just interpret it as seconds.
-fn raw_seconds(input: &str) -> IResult<&str, Duration> {
-    let (input, seconds) =
-        all_consuming(delimited(multispace0, float, multispace0)).parse(input)?;
-    Ok((input, Duration::Second(seconds)))
+/// Parse a duration string into a [`Container`], keeping each fragment as written.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input string is not a valid duration format.
+pub fn parse_container(input: &str) -> Result<Container, error::Error> {
+    parse_container_with(input, &Options::default())
 }
 
-fn full_duration(input: &str) -> IResult<&str, Vec<Duration>> {
-    all_consuming(many1(duration_fragment)).parse(input)
+/// Parse a duration string into a [`Container`] with the given options.
+///
+/// # Errors
+///
+/// See [`parse_container`].
+pub fn parse_container_with(input: &str, options: &Options) -> Result<Container, error::Error> {
+    let (_, container) = full_duration(options, input)
+        .map_err(nom::Err::<nom::error::Error<&str>>::to_owned)
+        .finish()
+        .map_err(parser_error)?;
+    Ok(container)
 }
 
-// Parse a duration
-fn duration(input: &str) -> IResult<&str, Container> {
-    complete(cut(alt((
-        map(raw_seconds, |v| Container::new(vec![v])),
-        map(full_duration, Container::new),
-    ))))
-    .parse(input)
+// Systemd accepts both the micro sign (U+00B5) and the Greek small letter mu (U+03BC) for
+// microseconds. These are the only non-ASCII characters the grammar knows about.
+const MICRO_SIGNS: [char; 2] = ['\u{b5}', '\u{3bc}'];
+
+// Check raw bytes only contain characters the grammar can use, and convert them to a string.
+fn from_bytes(input: &[u8]) -> Result<&str, error::Error> {
+    let input = std::str::from_utf8(input).map_err(|e| error::Error::InvalidByte {
+        byte: input[e.valid_up_to()],
+        offset: e.valid_up_to(),
+    })?;
+
+    match input
+        .char_indices()
+        .find(|&(_, c)| !c.is_ascii() && !MICRO_SIGNS.contains(&c))
+    {
+        Some((offset, _)) => Err(error::Error::InvalidByte {
+            byte: input.as_bytes()[offset],
+            offset,
+        }),
+        None => Ok(input),
+    }
 }
 
 macro_rules! impl_parse {
@@ -315,10 +385,57 @@ macro_rules! impl_parse {
                 "]"
             )]
             pub fn parse(input: &str) -> Result<$type, error::Error> {
-                let dur = duration(input).map_err(|e| e.to_owned()).finish()?;
-                let ret = dur.1.try_into()?;
+                parse_with(input, &Options::default())
+            }
+
+            #[doc = concat!(
+                "Parse a duration string into a [`",
+                stringify!($typename),
+                "`][",
+                stringify!($type),
+                "] with the given options.\n\n",
+                "# Errors\n\n",
+                "See [`parse`]."
+            )]
+            pub fn parse_with(input: &str, options: &Options) -> Result<$type, error::Error> {
+                let ret = parse_container_with(input, options)?.try_into()?;
                 Ok(ret)
             }
+
+            #[doc = concat!(
+                "Parse a duration from raw bytes into a [`",
+                stringify!($typename),
+                "`][",
+                stringify!($type),
+                "].\n\n",
+                "The input may contain ASCII and the UTF-8 encoded micro signs (`µ` and `μ`).\n\n",
+                "# Errors\n\n",
+                "Returns [`error::Error::InvalidByte`] if the input contains any other bytes, or\n",
+                "[`error::Error`] if the input is not a valid duration format or cannot be converted\n",
+                "into a [`",
+                stringify!($typename),
+                "`][",
+                stringify!($type),
+                "]."
+            )]
+            pub fn parse_bytes(input: &[u8]) -> Result<$type, error::Error> {
+                parse(from_bytes(input)?)
+            }
+
+            #[doc = concat!(
+                "Parse a duration from an [`OsStr`] into a [`",
+                stringify!($typename),
+                "`][",
+                stringify!($type),
+                "].\n\n",
+                "This is useful for environment variables and command line arguments, which are\n",
+                "not guaranteed to be valid UTF-8.\n\n",
+                "# Errors\n\n",
+                "See [`parse_bytes`]."
+            )]
+            pub fn parse_os_str(input: &OsStr) -> Result<$type, error::Error> {
+                parse_bytes(input.as_encoded_bytes())
+            }
         }
     };
 }
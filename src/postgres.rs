@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! `PostgreSQL` `interval` text, such as `1 year 2 mons 3 days 04:05:06.789` or `-00:00:30`.
+//!
+//! Both the `postgres` and `iso_8601` settings of `IntervalStyle` are parsed. Years and months are
+//! converted like `PostgreSQL`'s `EXTRACT(EPOCH FROM ...)`, so a year is 365.25 days and a month is
+//! 30 days.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::postgres;
+//!
+//! let container = postgres::parse("3 days 04:05:06.789").expect("Could not parse duration");
+//! assert_eq!(Duration::try_from(container.clone()).unwrap(), Duration::from_millis(273_906_789));
+//! assert_eq!(postgres::format(&container).unwrap(), "3 days 04:05:06.789");
+//! ```
+
+use nom::{
+    branch::alt,
+    bytes::complete::take_while_m_n,
+    character::complete::{alpha1, char, digit1, multispace0, multispace1, one_of},
+    combinator::{map, map_opt, opt, recognize},
+    error::{ErrorKind::Verify, ParseError},
+    multi::many0,
+    sequence::{delimited, preceded, terminated},
+    Err::Error,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration, Unit},
+    error, format, iso8601, parser,
+};
+
+// PostgreSQL's lengths of a year and a month, in days
+const DAYS_PER_YEAR: f64 = 365.25;
+const DAYS_PER_MONTH: f64 = 30.0;
+
+// A whole number with an optional sign
+fn integer<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, i64, E> {
+    map_opt(recognize((opt(one_of("+-")), digit1)), |s: &str| {
+        s.parse().ok()
+    })
+    .parse(input)
+}
+
+// A number of years, months or days, such as `2 mons`
+fn field<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Duration, E> {
+    #[allow(clippy::cast_precision_loss)]
+    map_opt(
+        (integer, multispace1, alpha1),
+        |(count, _, unit): (i64, _, &str)| {
+            let days = match unit {
+                "year" | "years" => DAYS_PER_YEAR,
+                "mon" | "mons" => DAYS_PER_MONTH,
+                "day" | "days" => 1.0,
+                _ => return None,
+            };
+            Some(Duration::Day(count as f64 * days))
+        },
+    )
+    .parse(input)
+}
+
+// Two digits counting minutes or seconds
+fn sexagesimal<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(
+        take_while_m_n(2, 2, |c: char| c.is_ascii_digit()),
+        |s: &str| s.parse().ok().filter(|&n| n < 60),
+    )
+    .parse(input)
+}
+
+// The time of an interval, such as `-04:05:06.789`. Hours may be any number.
+fn time<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Vec<Duration>, E> {
+    let (input, sign) = opt(one_of("+-")).parse(input)?;
+    let (input, hours) = map_opt(digit1, |s: &str| s.parse::<u64>().ok()).parse(input)?;
+    let (input, minutes) = preceded(char(':'), sexagesimal).parse(input)?;
+    let (input, seconds) = preceded(char(':'), sexagesimal).parse(input)?;
+    let (input, micros) = opt(preceded(
+        char('.'),
+        map_opt(
+            take_while_m_n(1, 6, |c: char| c.is_ascii_digit()),
+            |s: &str| format!("{s:0<6}").parse::<u32>().ok(),
+        ),
+    ))
+    .parse(input)?;
+
+    let sign = if sign == Some('-') { -1.0 } else { 1.0 };
+    #[allow(clippy::cast_precision_loss)]
+    let mut durations = vec![
+        Duration::Hour(sign * hours as f64),
+        Duration::Minute(sign * minutes as f64),
+        Duration::Second(sign * seconds as f64),
+    ];
+    if let Some(micros) = micros {
+        durations.push(Duration::Microsecond(sign * f64::from(micros)));
+    }
+
+    Ok((input, durations))
+}
+
+// Interval text in the postgres style, such as `1 year 2 mons 3 days 04:05:06`
+fn postgres_style<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let (input, mut durations) = many0(terminated(field, multispace0)).parse(input)?;
+    let (input, time) = opt(time).parse(input)?;
+
+    match time {
+        Some(time) => durations.extend(time),
+        None if durations.is_empty() => return Err(Error(E::from_error_kind(input, Verify))),
+        None => {}
+    }
+
+    Ok((input, Container::new(durations)))
+}
+
+// Interval text in the iso_8601 style, with PostgreSQL's lengths of years and months
+fn iso_8601_style<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    map(iso8601::iso8601, |container| {
+        Container::new(
+            container
+                .durations()
+                .iter()
+                .map(|&duration| match duration {
+                    Duration::Year(count) => Duration::Day(count * DAYS_PER_YEAR),
+                    Duration::Month(count) => Duration::Day(count * DAYS_PER_MONTH),
+                    duration => duration,
+                })
+                .collect(),
+        )
+    })
+    .parse(input)
+}
+
+/// Parse `PostgreSQL` interval text from the start of the input, returning the remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars. Both the `postgres` and
+/// `iso_8601` interval styles are accepted.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with interval text.
+pub fn postgres<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    alt((iso_8601_style, postgres_style)).parse(input)
+}
+
+/// Parse an entire string as `PostgreSQL` interval text, ignoring surrounding whitespace.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not interval text. See [`postgres`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(delimited(multispace0, postgres, multispace0), input)
+}
+
+/// Format a duration as interval text `PostgreSQL` accepts, such as `3 days 04:05:06.789`.
+///
+/// The duration is rounded to the nearest microsecond, as `PostgreSQL` stores intervals. Days are
+/// only shown for durations of a day or more, and negative durations have a sign on both the days
+/// and the time, as in `-1 days -02:00:00`.
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the duration is too large for an interval.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = container.nanos()?;
+    let sign = if nanos < 0 { "-" } else { "" };
+    let (micro, day) = (Unit::Microsecond.nanos(), Unit::Day.nanos());
+    let t = (nanos.unsigned_abs() + micro / 2) / micro * micro;
+
+    let days = t / day;
+    if i32::try_from(days).is_err() {
+        return Err(error::Error::DurationOverflow);
+    }
+    let clock = format::clock(sign, t % day, micro, Unit::Hour, false);
+
+    Ok(match days {
+        0 => clock,
+        1 if nanos > 0 => format!("1 day {clock}"),
+        days => format!("{sign}{days} days {clock}"),
+    })
+}
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Prometheus durations, as used in alerting rules and `PromQL`, such as `5m` or `1h30m`.
+//!
+//! The units are `y`, `w`, `d`, `h`, `m`, `s` and `ms`, each used at most once and in that order,
+//! with whole numbers only. Unlike systemd, a year is exactly 365 days. A lone `0` is also
+//! accepted.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::prometheus;
+//!
+//! assert_eq!(prometheus::to_systemd("1h30m").unwrap(), "1h 30min");
+//! assert_eq!(prometheus::from_systemd("2 weeks").unwrap(), "2w");
+//! ```
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1},
+    combinator::{map_opt, not, opt},
+    error::{ErrorKind::TooLarge, ParseError},
+    sequence::terminated,
+    Err::Failure,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{Container, Duration},
+    error, format, parser,
+};
+
+const MILLIS_PER_DAY: u64 = 86_400_000;
+
+// A whole number of any length
+fn number<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u64, E> {
+    map_opt(digit1, |s: &str| s.parse().ok()).parse(input)
+}
+
+/// Parse a Prometheus duration from the start of the input, returning the remaining input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars. Years are given as 365 days.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a Prometheus duration. Returns a
+/// [`Failure`] if the duration does not fit in an [`i64`] of nanoseconds, as in Prometheus.
+pub fn prometheus<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let start = input;
+    let (input, (years, weeks, days, hours, minutes, seconds, millis)) = (
+        opt(terminated(number, char('y'))),
+        opt(terminated(number, char('w'))),
+        opt(terminated(number, char('d'))),
+        opt(terminated(number, char('h'))),
+        opt(terminated(number, terminated(char('m'), not(char('s'))))),
+        opt(terminated(number, char('s'))),
+        opt(terminated(number, tag("ms"))),
+    )
+        .parse(input)?;
+
+    let components = [
+        (years, 365 * MILLIS_PER_DAY),
+        (weeks, 7 * MILLIS_PER_DAY),
+        (days, MILLIS_PER_DAY),
+        (hours, 3_600_000),
+        (minutes, 60_000),
+        (seconds, 1_000),
+        (millis, 1),
+    ];
+    if components.iter().all(|(count, _)| count.is_none()) {
+        let (input, _) = char('0').parse(input)?;
+        return Ok((input, Container::new(vec![Duration::Second(0.0)])));
+    }
+
+    // The total must fit in Go's time.Duration
+    let total = components
+        .iter()
+        .try_fold(0u64, |total, &(count, millis)| {
+            total.checked_add(count.unwrap_or(0).checked_mul(millis)?)
+        })
+        .filter(|&total| total <= i64::MAX.unsigned_abs() / 1_000_000);
+    if total.is_none() {
+        return Err(Failure(E::from_error_kind(start, TooLarge)));
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let durations = [
+        years.map(|n| Duration::Day(n as f64 * 365.0)),
+        weeks.map(|n| Duration::Week(n as f64)),
+        days.map(|n| Duration::Day(n as f64)),
+        hours.map(|n| Duration::Hour(n as f64)),
+        minutes.map(|n| Duration::Minute(n as f64)),
+        seconds.map(|n| Duration::Second(n as f64)),
+        millis.map(|n| Duration::Millisecond(n as f64)),
+    ];
+
+    Ok((
+        input,
+        Container::new(durations.into_iter().flatten().collect()),
+    ))
+}
+
+/// Parse an entire string as a Prometheus duration.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a Prometheus duration. See [`prometheus`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(prometheus, input)
+}
+
+/// Format a duration like Prometheus, such as `1h30m` or `90d`.
+///
+/// Years and weeks are only used if the duration is a whole number of them, as `90d` is easier to
+/// read than `12w6d`. Zero is `0s`.
+///
+/// # Errors
+///
+/// Returns [`error::Error::Unrepresentable`] if the duration is negative or not a whole number of
+/// milliseconds, and [`error::Error::DurationOverflow`] if it does not fit in an [`i64`] of
+/// nanoseconds.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let nanos = i64::try_from(container.nanos()?).map_err(|_| error::Error::DurationOverflow)?;
+    if nanos < 0 || nanos % 1_000_000 != 0 {
+        return Err(error::Error::Unrepresentable("Prometheus"));
+    }
+
+    let mut millis = nanos.unsigned_abs() / 1_000_000;
+    if millis == 0 {
+        return Ok("0s".to_owned());
+    }
+
+    let units = [
+        ("y", 365 * MILLIS_PER_DAY, true),
+        ("w", 7 * MILLIS_PER_DAY, true),
+        ("d", MILLIS_PER_DAY, false),
+        ("h", 3_600_000, false),
+        ("m", 60_000, false),
+        ("s", 1_000, false),
+        ("ms", 1, false),
+    ];
+    let mut parts = Vec::new();
+    for (name, size, exact) in units {
+        if exact && millis % size != 0 {
+            continue;
+        }
+        if millis >= size {
+            parts.push(format!("{}{name}", millis / size));
+            millis %= size;
+        }
+    }
+
+    Ok(parts.concat())
+}
+
+/// Convert a Prometheus duration into a systemd timespan, such as `1h30m` into `1h 30min`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a Prometheus duration.
+pub fn to_systemd(input: &str) -> Result<String, error::Error> {
+    format::systemd(&parse(input)?)
+}
+
+/// Convert a systemd timespan into a Prometheus duration, such as `1h 30min` into `1h30m`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a systemd duration, or cannot be represented in
+/// Prometheus. See [`format()`].
+pub fn from_systemd(input: &str) -> Result<String, error::Error> {
+    format(&parser::parse_container(input)?)
+}
@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Protocol Buffers `google.protobuf.Duration`, as [`prost_types::Duration`] and in its JSON form,
+//! such as `1.500s`.
+//!
+//! A protobuf duration is whole seconds and nanoseconds of the same sign, up to about 10,000 years
+//! either way. Conversions into it are checked against that range. The JSON form is a number of
+//! seconds with up to nine decimal places, followed by `s`.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use systemd_duration::protobuf::{self, ProtobufExt};
+//!
+//! let duration = Duration::from_millis(1_500).to_protobuf().unwrap();
+//! assert_eq!((duration.seconds, duration.nanos), (1, 500_000_000));
+//!
+//! assert_eq!(protobuf::to_systemd("90.5s").unwrap(), "1min 30.5s");
+//! assert_eq!(protobuf::from_systemd("1min 30.5s").unwrap(), "90.500s");
+//! ```
+
+use nom::{
+    bytes::complete::take_while_m_n,
+    character::complete::{char, digit1, multispace0},
+    combinator::{map_opt, opt},
+    error::{ErrorKind::TooLarge, ParseError},
+    sequence::{delimited, preceded},
+    Err::Failure,
+    IResult, Parser,
+};
+
+use crate::{
+    duration::{protobuf::from_nanos, Container},
+    error, format, parser,
+};
+
+/// Extension trait for converting durations into [`prost_types::Duration`].
+pub trait ProtobufExt {
+    /// Convert the duration into a [`prost_types::Duration`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`error::Error::DurationOverflow`] if the duration is beyond protobuf's range of
+    /// about 10,000 years.
+    fn to_protobuf(&self) -> Result<prost_types::Duration, error::Error>;
+}
+
+impl ProtobufExt for Container {
+    fn to_protobuf(&self) -> Result<prost_types::Duration, error::Error> {
+        from_nanos(self.nanos()?)
+    }
+}
+
+impl ProtobufExt for std::time::Duration {
+    fn to_protobuf(&self) -> Result<prost_types::Duration, error::Error> {
+        // At most about 1.8e28, which fits easily
+        #[allow(clippy::cast_possible_wrap)]
+        from_nanos(self.as_nanos() as i128)
+    }
+}
+
+#[cfg(feature = "with-chrono")]
+impl ProtobufExt for ::chrono::TimeDelta {
+    fn to_protobuf(&self) -> Result<prost_types::Duration, error::Error> {
+        from_nanos(i128::from(self.num_seconds()) * 1_000_000_000 + i128::from(self.subsec_nanos()))
+    }
+}
+
+#[cfg(feature = "with-time")]
+impl ProtobufExt for ::time::Duration {
+    fn to_protobuf(&self) -> Result<prost_types::Duration, error::Error> {
+        from_nanos(self.whole_nanoseconds())
+    }
+}
+
+/// Parse a protobuf duration in its JSON form from the start of the input, returning the remaining
+/// input.
+///
+/// This is a [`nom`] parser, and can be embedded in other grammars.
+///
+/// # Errors
+///
+/// Returns an error if the input does not start with a JSON duration. Returns a [`Failure`] if the
+/// duration is beyond protobuf's range.
+pub fn protobuf<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Container, E> {
+    let start = input;
+    let (input, negative) = opt(char('-')).parse(input)?;
+    let (input, seconds) = map_opt(digit1, |s: &str| s.parse::<i64>().ok()).parse(input)?;
+    let (input, nanos) = opt(preceded(
+        char('.'),
+        map_opt(
+            take_while_m_n(1, 9, |c: char| c.is_ascii_digit()),
+            |s: &str| format!("{s:0<9}").parse::<i32>().ok(),
+        ),
+    ))
+    .parse(input)?;
+    let (input, _) = char('s').parse(input)?;
+
+    let sign = if negative.is_some() { -1 } else { 1 };
+    let nanos =
+        i128::from(sign) * (i128::from(seconds) * 1_000_000_000 + i128::from(nanos.unwrap_or(0)));
+    let duration = from_nanos(nanos).map_err(|_| Failure(E::from_error_kind(start, TooLarge)))?;
+
+    Ok((input, Container::from(duration)))
+}
+
+/// Parse an entire string as a protobuf duration in its JSON form, ignoring surrounding
+/// whitespace.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a JSON duration. See [`protobuf`].
+pub fn parse(input: &str) -> Result<Container, error::Error> {
+    parser::parse_all(delimited(multispace0, protobuf, multispace0), input)
+}
+
+/// Format a duration in the canonical JSON form of a protobuf duration, such as `1.500s`.
+///
+/// The fraction has three, six or nine digits, as few as are needed, and is left out for whole
+/// seconds.
+///
+/// # Errors
+///
+/// Returns [`error::Error::DurationOverflow`] if the duration is beyond protobuf's range.
+pub fn format(container: &Container) -> Result<String, error::Error> {
+    let duration = container.to_protobuf()?;
+    let sign = if duration.seconds < 0 || duration.nanos < 0 {
+        "-"
+    } else {
+        ""
+    };
+    let (seconds, nanos) = (
+        duration.seconds.unsigned_abs(),
+        duration.nanos.unsigned_abs(),
+    );
+
+    Ok(match nanos {
+        0 => format!("{sign}{seconds}s"),
+        _ if nanos % 1_000_000 == 0 => format!("{sign}{seconds}.{:03}s", nanos / 1_000_000),
+        _ if nanos % 1_000 == 0 => format!("{sign}{seconds}.{:06}s", nanos / 1_000),
+        _ => format!("{sign}{seconds}.{nanos:09}s"),
+    })
+}
+
+/// Convert a protobuf JSON duration into a systemd timespan, such as `90s` into `1min 30s`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a JSON duration.
+pub fn to_systemd(input: &str) -> Result<String, error::Error> {
+    format::systemd(&parse(input)?)
+}
+
+/// Convert a systemd timespan into a protobuf JSON duration, such as `1min 30s` into `90s`.
+///
+/// # Errors
+///
+/// Returns [`error::Error`] if the input is not a systemd duration, or is beyond protobuf's range.
+/// See [`format()`].
+pub fn from_systemd(input: &str) -> Result<String, error::Error> {
+    format(&parser::parse_container(input)?)
+}
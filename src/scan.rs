@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Finding durations embedded in free text, such as log lines.
+//!
+//! # Example
+//! ```
+//! let text = "restart took 1min 3.5s, retrying in 30s";
+//! let found: Vec<_> = systemd_duration::scan::scan(text).map(|m| m.as_str()).collect();
+//! assert_eq!(found, ["1min 3.5s", "30s"]);
+//! ```
+
+use std::ops::Range;
+
+use crate::{
+    duration::Container,
+    parser::{aliased_fragment, Options},
+};
+
+/// A duration found in a larger piece of text.
+#[derive(Clone, Debug)]
+pub struct Match<'a> {
+    text: &'a str,
+    span: Range<usize>,
+    container: Container,
+}
+
+impl<'a> Match<'a> {
+    /// The matched text.
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.span.clone()]
+    }
+
+    /// The byte range of the match in the scanned text.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The parsed duration.
+    #[must_use]
+    pub const fn container(&self) -> &Container {
+        &self.container
+    }
+
+    /// Take the parsed duration.
+    #[must_use]
+    pub fn into_container(self) -> Container {
+        self.container
+    }
+}
+
+/// An iterator over the durations in a piece of text, created by [`scan`].
+#[derive(Clone, Debug)]
+pub struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+/// Find every duration in the given text.
+///
+/// Each match is as long as possible, so `1min 3.5s` and `1h30m` are each one match. Only
+/// durations with units are matched, as bare numbers are rarely durations in free text. Words
+/// which only start like a duration, such as `5mph`, are skipped.
+#[must_use]
+pub const fn scan(text: &str) -> Scanner<'_> {
+    Scanner { text, pos: 0 }
+}
+
+// Whether the character is part of a word, so a duration can't start or end next to it
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Parse as many fragments as possible from the start of the input, such as `1h30m`, then back
+// off to the last one which doesn't run into a word. Returns the number of bytes consumed.
+fn fragments(input: &str) -> Option<(usize, Container)> {
+    let (mut durations, mut aliases, mut ends) = (Vec::new(), Vec::new(), Vec::new());
+    let mut rest = input;
+    while let Ok((next, (duration, alias))) = aliased_fragment::<()>(&Options::default(), rest) {
+        // A unit which runs into a letter is only the start of a word, as in `5mph`
+        if next.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            break;
+        }
+        durations.push(duration);
+        aliases.push(alias);
+        ends.push(input.len() - next.len());
+        rest = next;
+    }
+
+    while ends
+        .last()
+        .is_some_and(|&end| input[end..].starts_with(is_word_char))
+    {
+        durations.pop();
+        aliases.pop();
+        ends.pop();
+    }
+
+    ends.last()
+        .map(|&end| (end, Container::with_aliases(durations, aliases)))
+}
+
+impl<'a> Iterator for Scanner<'a> {
+    type Item = Match<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(c) = self.text[self.pos..].chars().next() {
+            let start = self.pos;
+            self.pos += c.len_utf8();
+
+            // Only try to match at the start of something that looks like a number
+            if !(c.is_ascii_digit() || matches!(c, '.' | '+' | '-')) {
+                continue;
+            }
+            if self.text[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|p| is_word_char(p) || p == '.')
+            {
+                continue;
+            }
+
+            if let Some((len, container)) = fragments(&self.text[start..]) {
+                self.pos = start + len;
+                return Some(Match {
+                    text: self.text,
+                    span: start..self.pos,
+                    container,
+                });
+            }
+        }
+
+        None
+    }
+}
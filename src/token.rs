@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: CC0-1.0
+//
+// This file is part of systemd-duration.
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see <https://creativecommons.org/publicdomain/zero/1.0/>.
+
+//! Splitting duration strings into tokens, such as for syntax highlighting.
+//!
+//! # Example
+//! ```
+//! use systemd_duration::{duration::Unit, token::{tokenize, TokenKind}};
+//!
+//! let kinds: Vec<_> = tokenize("1h 30x").map(|t| t.kind).collect();
+//! assert_eq!(
+//!     kinds,
+//!     [
+//!         TokenKind::Number,
+//!         TokenKind::Unit(Unit::Hour),
+//!         TokenKind::Whitespace,
+//!         TokenKind::Number,
+//!         TokenKind::Invalid,
+//!     ]
+//! );
+//! ```
+
+use std::ops::Range;
+
+use crate::{duration::Unit, parser};
+
+/// The kind of a [`Token`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A number, such as `30` or `1.5`
+    Number,
+    /// A unit, such as `min`
+    Unit(Unit),
+    /// A `+` or `-` sign
+    Sign,
+    /// Whitespace between other tokens
+    Whitespace,
+    /// Anything else, including words which aren't units
+    Invalid,
+}
+
+/// A token in a duration string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    /// What the token is
+    pub kind: TokenKind,
+    /// The byte range of the token in the input
+    pub span: Range<usize>,
+}
+
+/// An iterator over the tokens in a duration string, created by [`tokenize`].
+#[derive(Clone, Debug)]
+pub struct Tokens<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+/// Split a duration string into tokens.
+///
+/// This never fails: anything which can't be part of a duration is returned as
+/// [`TokenKind::Invalid`], so partially typed input can still be tokenized. The tokens cover the
+/// whole input, in order.
+///
+/// Tokens are only checked individually, so the sequence of tokens may still not be a valid
+/// duration (for example, a unit with no number before it).
+#[must_use]
+pub const fn tokenize(input: &str) -> Tokens<'_> {
+    Tokens { input, pos: 0 }
+}
+
+// Same whitespace as the parser accepts
+const fn is_space(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\r' | '\n')
+}
+
+// Length of the leading run of characters matching the predicate
+fn run_len(input: &str, pred: impl Fn(char) -> bool) -> usize {
+    input.find(|c| !pred(c)).unwrap_or(input.len())
+}
+
+// Length of the number at the start of the input, if any
+fn number_len(input: &str) -> usize {
+    let int_len = run_len(input, |c| c.is_ascii_digit());
+    let frac_len = input[int_len..]
+        .strip_prefix('.')
+        .map_or(0, |frac| run_len(frac, |c| c.is_ascii_digit()));
+    match frac_len {
+        0 => int_len,
+        frac_len => int_len + 1 + frac_len,
+    }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.input[self.pos..];
+        let c = rest.chars().next()?;
+
+        let (kind, len) = if is_space(c) {
+            (TokenKind::Whitespace, run_len(rest, is_space))
+        } else if c == '+' || c == '-' {
+            (TokenKind::Sign, 1)
+        } else if c.is_alphabetic() {
+            let len = run_len(rest, char::is_alphabetic);
+            let kind = parser::unit(&rest[..len]).map_or(TokenKind::Invalid, TokenKind::Unit);
+            (kind, len)
+        } else {
+            match number_len(rest) {
+                0 => (
+                    TokenKind::Invalid,
+                    run_len(rest, |c| {
+                        !(is_space(c) || c.is_alphanumeric() || matches!(c, '+' | '-' | '.'))
+                    })
+                    .max(c.len_utf8()),
+                ),
+                len => (TokenKind::Number, len),
+            }
+        };
+
+        let start = self.pos;
+        self.pos += len;
+        Some(Token {
+            kind,
+            span: start..self.pos,
+        })
+    }
+}